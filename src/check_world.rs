@@ -0,0 +1,74 @@
+// Copyright (c) 2023 Yuichi Ishida
+//
+// Released under the MIT license.
+// see https://opensource.org/licenses/mit-license.php
+
+use crate::error::GameSystemError;
+use crate::game_system::player_status::PlayerStatus;
+use crate::game_system::toml_interface::read_world_from_file;
+use crate::game_system::world::World;
+use crate::preferences::{Language, Preferences};
+use anyhow::Result;
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+pub fn run(world_file_path: PathBuf) -> Result<()> {
+    let preferences: Preferences = Default::default();
+    let world = read_world_from_file(&world_file_path)?;
+    let warning_list = world.warning_list(&preferences);
+    let can_reach_goal = world.can_reach_goal()?;
+    if warning_list.is_empty() && can_reach_goal {
+        match preferences.language() {
+            Language::Japanese => println!("問題は見つかりませんでした。"),
+        }
+    }
+    for warning in warning_list {
+        match preferences.language() {
+            Language::Japanese => println!("警告: {}", warning),
+        }
+    }
+    for (area_index, preview) in preview_list(&world, &preferences)? {
+        match preferences.language() {
+            Language::Japanese => println!("{}マス目: {}", area_index, preview),
+        }
+    }
+    for (source_index, target_index) in world.jump_edges() {
+        match preferences.language() {
+            Language::Japanese => println!(
+                "{}マス目 → {}マス目（決まったマスへの移動）",
+                source_index, target_index
+            ),
+        }
+    }
+    if !can_reach_goal {
+        return Err(GameSystemError::UnreachableGoal.into());
+    }
+    Ok(())
+}
+
+/// 一人で各マスに止まった場合の効果の結果の一覧
+fn preview_list(world: &World, preferences: &Preferences) -> Result<Vec<(usize, String)>> {
+    let goal_position = world.area_list().len() - 1;
+    let player = match preferences.language() {
+        Language::Japanese => "プレイヤー".to_string(),
+    };
+    let player_order = vec![player.clone()];
+    let mut preview_list = Vec::new();
+    for (area_index, area) in world.area_list().iter().enumerate() {
+        let mut player_status = PlayerStatus::default();
+        player_status.set_position(area_index);
+        let player_status_table = HashMap::from([(player.clone(), player_status)]);
+        for preview in area.preview(
+            preferences,
+            &player,
+            &player_order,
+            &player_status_table,
+            world.flag_table(),
+            area_index,
+            goal_position,
+        )? {
+            preview_list.push((area_index, preview));
+        }
+    }
+    Ok(preview_list)
+}
@@ -3,9 +3,12 @@
 // Released under the MIT license.
 // see https://opensource.org/licenses/mit-license.php
 
+use crate::preferences::Preferences;
 use anyhow::Result;
+use clap::builder::RangedU64ValueParser;
 use clap::{Parser, Subcommand, ValueHint};
 use std::path::PathBuf;
+use std::time::Duration;
 
 impl Cli {
     pub fn run() -> Result<()> {
@@ -13,14 +16,52 @@ impl Cli {
             Action::Game {
                 player_list_file,
                 world_file,
+                auto_advance,
+                trace_ui,
+                practice,
+                hide_effects,
             } => {
-                crate::user_interface::tui::run(Default::default(), player_list_file, world_file)?;
+                let mut preferences = Preferences::default();
+                preferences.set_auto_advance_delay(auto_advance.map(Duration::from_secs));
+                preferences.set_reveal_effects(!hide_effects);
+                crate::user_interface::tui::run(
+                    preferences,
+                    player_list_file,
+                    world_file,
+                    trace_ui,
+                    practice,
+                )?;
                 Ok(())
             }
             Action::WorldToTex { world_file } => {
                 crate::world_to_tex::run(world_file)?;
                 Ok(())
             }
+            Action::WorldToCsv { world_file } => {
+                crate::world_to_csv::run(world_file)?;
+                Ok(())
+            }
+            Action::Generate {
+                areas,
+                dice_max,
+                output,
+                seed,
+            } => {
+                crate::generate_world::run(areas, dice_max, output, seed)?;
+                Ok(())
+            }
+            Action::Check { world_file } => {
+                crate::check_world::run(world_file)?;
+                Ok(())
+            }
+            Action::ShowPlayers { player_list_file } => {
+                crate::show_players::run(player_list_file)?;
+                Ok(())
+            }
+            Action::ListEffects => {
+                crate::list_effects::run()?;
+                Ok(())
+            }
         }
     }
 }
@@ -39,9 +80,47 @@ enum Action {
         player_list_file: PathBuf,
         #[clap(value_hint(ValueHint::FilePath))]
         world_file: PathBuf,
+        /// Automatically advance from the dice result after the given seconds
+        #[clap(long, value_name = "SECONDS")]
+        auto_advance: Option<u64>,
+        /// Write every UI state transition to the given file on exit
+        #[clap(long, value_name = "FILE", value_hint(ValueHint::FilePath))]
+        trace_ui: Option<PathBuf>,
+        /// Practice alone, restarting from the start each time the goal is reached
+        #[clap(long)]
+        practice: bool,
+        /// Hide area effects until a player lands on the area
+        #[clap(long)]
+        hide_effects: bool,
     },
     WorldToTex {
         #[clap(value_hint(ValueHint::FilePath))]
         world_file: PathBuf,
     },
+    WorldToCsv {
+        #[clap(value_hint(ValueHint::FilePath))]
+        world_file: PathBuf,
+    },
+    /// Generate a random world file whose goal is reachable
+    Generate {
+        /// Number of areas between the start and the goal
+        #[clap(long, default_value_t = 30, value_parser = RangedU64ValueParser::<usize>::new().range(1..))]
+        areas: usize,
+        #[clap(long, default_value_t = 6)]
+        dice_max: usize,
+        #[clap(value_hint(ValueHint::FilePath))]
+        output: PathBuf,
+        /// Seed of the random generator (chosen randomly if omitted)
+        #[clap(long)]
+        seed: Option<u64>,
+    },
+    Check {
+        #[clap(value_hint(ValueHint::FilePath))]
+        world_file: PathBuf,
+    },
+    ShowPlayers {
+        #[clap(value_hint(ValueHint::FilePath))]
+        player_list_file: PathBuf,
+    },
+    ListEffects,
 }
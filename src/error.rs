@@ -17,4 +17,18 @@ pub enum GameSystemError {
     OutOfRangePosition(String, usize),
     #[error("There is no player")]
     NoPlayer,
+    #[error("This world needs at least {0} players, but there are {1}")]
+    TooFewPlayers(usize, usize),
+    #[error("Area {0} is a goal tier, so it must not have effects")]
+    EffectOnGoalTier(usize),
+    #[error("Practice mode needs exactly one player, but there are {0}")]
+    NotSinglePlayer(usize),
+    #[error("No player can reach the goal in this world")]
+    UnreachableGoal,
+    #[error("A world needs at least one area between the start and the goal")]
+    NoArea,
+    #[error("duplicate label: {0}")]
+    DuplicateLabel(String),
+    #[error("Label is not found: {0}")]
+    NotFoundLabel(String),
 }
@@ -3,7 +3,7 @@
 // Released under the MIT license.
 // see https://opensource.org/licenses/mit-license.php
 
-mod area;
+pub mod area;
 pub mod player_status;
 pub mod toml_interface;
 pub mod world;
@@ -7,7 +7,9 @@ use crate::error::GameSystemError;
 use crate::game_system::player_status::PlayerStatus;
 use crate::preferences::{Language, Preferences};
 use anyhow::{anyhow, Context};
-use rand::rngs::ThreadRng;
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+use rand::{RngCore, SeedableRng};
 use std::collections::HashMap;
 use std::str::FromStr;
 
@@ -15,30 +17,234 @@ use std::str::FromStr;
 #[derive(Debug)]
 pub struct Area {
     description: String,
+    localized_description_table: HashMap<Language, String>,
     effect_list: Vec<Box<dyn AreaEffect>>,
+    effect_source_list: Vec<String>,
+    goal_bonus: Option<u32>,
+    label: Option<String>,
 }
 
 impl Area {
     pub fn new(description: String, effect_list: Vec<Box<dyn AreaEffect>>) -> Self {
         Self {
             description,
+            localized_description_table: HashMap::new(),
             effect_list,
+            effect_source_list: Vec::new(),
+            goal_bonus: None,
+            label: None,
         }
     }
+    /// 盤面ファイルに書かれた、言語ごとの説明を除いたマスの説明
+    pub fn description(&self) -> &str {
+        &self.description
+    }
+    /// 盤面ファイルに書かれた効果の入力文字列の一覧
+    pub fn effect_source_list(&self) -> &[String] {
+        &self.effect_source_list
+    }
+    pub fn set_effect_source_list(&mut self, effect_source_list: Vec<String>) {
+        self.effect_source_list = effect_source_list;
+    }
+    /// `language`向けに盤面ファイルで設定された説明
+    pub fn localized_description(&self, language: Language) -> Option<&str> {
+        self.localized_description_table
+            .get(&language)
+            .map(String::as_str)
+    }
+    /// 言語ごとの説明を設定する（設定のない言語では`description`を用いる）
+    pub fn set_localized_description(&mut self, language: Language, description: String) {
+        self.localized_description_table
+            .insert(language, description);
+    }
+    /// 止まるとゴールになるマスの場合、ゴールした際に得られる得点
+    pub fn goal_bonus(&self) -> Option<u32> {
+        self.goal_bonus
+    }
+    pub fn set_goal_bonus(&mut self, goal_bonus: Option<u32>) {
+        self.goal_bonus = goal_bonus;
+    }
+    /// 効果から移動先として指定するためのマスの名前
+    pub fn label(&self) -> Option<&str> {
+        self.label.as_deref()
+    }
+    pub fn set_label(&mut self, label: Option<String>) {
+        self.label = label;
+    }
+    /// 効果が指定しているラベルを`label_table`からマスの番号に解決する
+    pub fn resolve_label(
+        &mut self,
+        label_table: &HashMap<String, usize>,
+    ) -> Result<(), GameSystemError> {
+        for effect in self.effect_list.iter_mut() {
+            effect.resolve_label(label_table)?;
+        }
+        Ok(())
+    }
+    /// ゴールが`goal_position`番目である盤面の`area_index`番目のマスとして効果を発動し、現在のプレイヤーがこのマスを訪れたことを記録する
+    #[allow(clippy::too_many_arguments)]
     pub fn execute(
         &self,
         current_player: &str,
         player_order: &[String],
         player_status_table: &mut HashMap<String, PlayerStatus>,
-        rng: &mut ThreadRng,
+        rng: &mut dyn RngCore,
+        flag_table: &mut HashMap<String, bool>,
+        area_index: usize,
+        goal_position: usize,
     ) -> Result<(), GameSystemError> {
+        // 効果を発動する前に、以前にこのマスに止まったプレイヤーを調べておく
+        let haunted_player = if self.effect_list.iter().any(|effect| effect.is_haunt()) {
+            find_haunted_player(
+                current_player,
+                player_order,
+                player_status_table,
+                area_index,
+            )?
+        } else {
+            None
+        };
         for effect in self.effect_list.iter() {
-            effect.execute(current_player, player_order, player_status_table, rng, "")?;
+            execute_effect(
+                effect.as_ref(),
+                current_player,
+                player_order,
+                player_status_table,
+                rng,
+                flag_table,
+                area_index,
+                goal_position,
+            )?;
+        }
+        if let Some(haunted_player) = haunted_player {
+            for effect in self.effect_list.iter().filter(|effect| !effect.is_haunt()) {
+                execute_effect(
+                    effect.as_ref(),
+                    &haunted_player,
+                    player_order,
+                    player_status_table,
+                    rng,
+                    flag_table,
+                    area_index,
+                    goal_position,
+                )?;
+            }
         }
+        player_status_table
+            .get_mut(current_player)
+            .ok_or_else(|| GameSystemError::NotFoundPlayer(current_player.to_owned()))?
+            .visit(area_index);
         Ok(())
     }
+    /// 状態を変更せずに、効果を発動した場合にどうなるかを説明する
+    ///
+    /// 状態を複製して効果を発動し、変化のあったプレイヤーごとに一行の説明を返す。
+    /// ゲームの乱数を進めないよう別の乱数を使うため、ランダムな効果の場合は結果の一例となる。
+    #[allow(clippy::too_many_arguments)]
+    pub fn preview(
+        &self,
+        preferences: &Preferences,
+        current_player: &str,
+        player_order: &[String],
+        player_status_table: &HashMap<String, PlayerStatus>,
+        flag_table: &HashMap<String, bool>,
+        area_index: usize,
+        goal_position: usize,
+    ) -> Result<Vec<String>, GameSystemError> {
+        let mut previewed_player_status_table = player_status_table.clone();
+        self.execute(
+            current_player,
+            player_order,
+            &mut previewed_player_status_table,
+            &mut StdRng::from_entropy(),
+            &mut flag_table.clone(),
+            area_index,
+            goal_position,
+        )?;
+        let mut preview_list = Vec::new();
+        for player in player_order {
+            let before = player_status_table
+                .get(player)
+                .ok_or_else(|| GameSystemError::NotFoundPlayer(player.to_owned()))?;
+            let after = previewed_player_status_table
+                .get(player)
+                .ok_or_else(|| GameSystemError::NotFoundPlayer(player.to_owned()))?;
+            if before.position() != after.position() {
+                preview_list.push(match preferences.language() {
+                    Language::Japanese => format!(
+                        "{}: {} マス目から {} マス目へ移動する。",
+                        player,
+                        before.position(),
+                        after.position()
+                    ),
+                });
+            }
+            if before.num_skip() != after.num_skip() {
+                preview_list.push(match preferences.language() {
+                    Language::Japanese => format!(
+                        "{}: 休みが {} 回から {} 回になる。",
+                        player,
+                        before.num_skip(),
+                        after.num_skip()
+                    ),
+                });
+            }
+        }
+        Ok(preview_list)
+    }
+    /// `area_index`番目のマスとして、決まったマスへ移動させる効果の移動先の一覧
+    pub fn jump_target_list(&self, area_index: usize, goal_position: usize) -> Vec<usize> {
+        self.effect_list
+            .iter()
+            .filter_map(|effect| effect.jump_target(area_index, goal_position))
+            .collect()
+    }
+    /// 止まったときに先のマスの一覧を表示するかどうか
+    pub fn show_map(&self) -> bool {
+        self.effect_list.iter().any(|effect| effect.show_map())
+    }
+    /// 先のマスの一覧で表示する一行の説明（長い説明は`max_len`文字で切り詰め、`reveal_effects`が無効の場合は効果を伏せる）
+    pub fn map_line(&self, preferences: &Preferences, max_len: usize) -> String {
+        let mut line: String = self
+            .description_text(preferences)
+            .lines()
+            .next()
+            .unwrap_or("")
+            .to_string();
+        if line.chars().count() > max_len {
+            line = line.chars().take(max_len).collect();
+            line += "…";
+        }
+        if preferences.reveal_effects() {
+            for effect in self.effect_list.iter() {
+                line += " / ";
+                line += &effect.effect_text(preferences);
+            }
+        }
+        if let Some(goal_bonus) = self.goal_bonus {
+            match preferences.language() {
+                Language::Japanese => line += &format!(" / ゴール（ボーナス: {}点）", goal_bonus),
+            }
+        }
+        line
+    }
+    /// 効果を含まないマスの説明
+    pub fn description_text(&self, preferences: &Preferences) -> String {
+        self.localized_description_table
+            .get(&preferences.language())
+            .unwrap_or(&self.description)
+            .clone()
+    }
+    /// サイコロを振る前に表示するマスの説明（`reveal_effects`が無効の場合は効果を伏せる）
+    pub fn preview_description(&self, preferences: &Preferences) -> String {
+        if preferences.reveal_effects() {
+            self.area_description(preferences)
+        } else {
+            self.description_text(preferences)
+        }
+    }
     pub fn area_description(&self, preferences: &Preferences) -> String {
-        let mut text = self.description.clone();
+        let mut text = self.description_text(preferences);
         text += "\n\n";
         match preferences.language() {
             Language::Japanese => text += "効果\n",
@@ -48,21 +254,128 @@ impl Area {
             text += &effect.effect_text(preferences);
             text += "\n";
         }
+        if let Some(goal_bonus) = self.goal_bonus {
+            match preferences.language() {
+                Language::Japanese => {
+                    text += &format!("- ここでゴールする（ボーナス: {}点）。\n", goal_bonus)
+                }
+            }
+        }
         text
     }
 }
 
+/// 効果を一つ発動し、拒否権による取り消しを処理する
+#[allow(clippy::too_many_arguments)]
+fn execute_effect(
+    effect: &dyn AreaEffect,
+    player: &str,
+    player_order: &[String],
+    player_status_table: &mut HashMap<String, PlayerStatus>,
+    rng: &mut dyn RngCore,
+    flag_table: &mut HashMap<String, bool>,
+    area_index: usize,
+    goal_position: usize,
+) -> Result<(), GameSystemError> {
+    let previous_player_status_table = player_status_table.clone();
+    effect.execute(
+        player,
+        player_order,
+        player_status_table,
+        rng,
+        flag_table,
+        area_index,
+        goal_position,
+        "",
+    )?;
+    cancel_vetoed_effect(
+        player_order,
+        &previous_player_status_table,
+        player_status_table,
+    )
+}
+
+/// `area_index`番目のマスに最後に止まった、ゴールしていない他のプレイヤー
+fn find_haunted_player(
+    current_player: &str,
+    player_order: &[String],
+    player_status_table: &HashMap<String, PlayerStatus>,
+    area_index: usize,
+) -> Result<Option<String>, GameSystemError> {
+    for player in player_order {
+        let player_status = player_status_table
+            .get(player)
+            .ok_or_else(|| GameSystemError::NotFoundPlayer(player.to_owned()))?;
+        if player != current_player
+            && player_status.order_of_arrival().is_none()
+            && player_status.is_haunting(area_index)
+        {
+            return Ok(Some(player.to_owned()));
+        }
+    }
+    Ok(None)
+}
+
+/// 拒否権を持つプレイヤーの状態が効果で変化した場合、拒否権を消費して効果を取り消す
+fn cancel_vetoed_effect(
+    player_order: &[String],
+    previous_player_status_table: &HashMap<String, PlayerStatus>,
+    player_status_table: &mut HashMap<String, PlayerStatus>,
+) -> Result<(), GameSystemError> {
+    for player in player_order {
+        let previous_player_status = previous_player_status_table
+            .get(player)
+            .ok_or_else(|| GameSystemError::NotFoundPlayer(player.to_owned()))?;
+        let player_status = player_status_table
+            .get_mut(player)
+            .ok_or_else(|| GameSystemError::NotFoundPlayer(player.to_owned()))?;
+        if previous_player_status.has_veto()
+            && player_status.is_changed_by_effect(previous_player_status)
+        {
+            *player_status = previous_player_status.clone();
+            player_status.set_veto(false);
+        }
+    }
+    Ok(())
+}
+
 /// マスの持つ効果
 pub trait AreaEffect: core::fmt::Debug {
     /// 効果発動の際にユーザ入力が必要かどうか
     fn need_argument(&self) -> bool;
     fn effect_text(&self, preferences: &Preferences) -> String;
+    /// 効果一覧などで表示される詳しい説明
+    fn help_text(&self, preferences: &Preferences) -> String;
+    /// 止まったときに先のマスの一覧を表示するかどうか
+    fn show_map(&self) -> bool {
+        false
+    }
+    /// 以前にこのマスに止まったプレイヤーにも他の効果を発動させるかどうか
+    fn is_haunt(&self) -> bool {
+        false
+    }
+    /// `area_index`番目のマスに止まったプレイヤーを必ず決まったマスへ移動させる場合、その移動先
+    fn jump_target(&self, _area_index: usize, _goal_position: usize) -> Option<usize> {
+        None
+    }
+    /// 盤面を作り終えた後に、ラベルで指定されたマスを`label_table`から番号に解決する
+    fn resolve_label(
+        &mut self,
+        _label_table: &HashMap<String, usize>,
+    ) -> Result<(), GameSystemError> {
+        Ok(())
+    }
+    /// `flag_table`は盤面全体で共有する名前付きのフラグ
+    #[allow(clippy::too_many_arguments)]
     fn execute(
         &self,
         current_player: &str,
         player_order: &[String],
         player_status_table: &mut HashMap<String, PlayerStatus>,
-        rng: &mut ThreadRng,
+        rng: &mut dyn RngCore,
+        flag_table: &mut HashMap<String, bool>,
+        area_index: usize,
+        goal_position: usize,
         arguments: &str,
     ) -> Result<(), GameSystemError>;
 }
@@ -70,46 +383,72 @@ pub trait AreaEffect: core::fmt::Debug {
 impl FromStr for Box<dyn AreaEffect> {
     type Err = anyhow::Error;
     fn from_str(area_effect_str: &str) -> Result<Self, Self::Err> {
-        macro_rules! parse_effect {
-            ($effect_name_str:expr, $effect_parameters_str: expr, $($effect_names:ident),+) => {
-                match $effect_name_str.as_str() {
-                    $(stringify!($effect_names) => {
-                        Ok(Box::new(
-                                $effect_names::from_str($effect_parameters_str)
-                                .with_context(||
-                                    format!("faied to parse {} (the correct format is {})", stringify!($effect_names), $effect_names::input_format())
-                                )?
-                        ))
-                    }),+
-                    _ => Err(GameSystemError::NotFoundAreaType($effect_name_str.to_owned()).into()),
-                }
-            };
-        }
-        let area_effect_strings: Vec<_> = area_effect_str
-            .replace(char::is_whitespace, "")
-            .split(':')
-            .map(|s| s.to_owned())
-            .collect();
-        if area_effect_strings.len() != 2 {
-            return Err(anyhow!(
+        let (effect_name, effect_parameters) = area_effect_str.split_once(':').ok_or_else(|| {
+            anyhow!(
                 "failed to parse an area effect (the correct format is `EffectName: [parameters...]`)."
-            ));
-        }
-        let effect_name = area_effect_strings.get(0).unwrap();
-        let effect_parameters = area_effect_strings.get(1).unwrap();
-        parse_effect!(
-            effect_name,
-            effect_parameters,
-            GoToStart,
-            SkipSelf,
-            PushSelf,
-            PushOthersAll,
-            PullSelf,
-            PullOthersAll
-        )
+            )
+        })?;
+        parse_area_effect(&remove_whitespace(effect_name), effect_parameters.trim())
     }
 }
 
+macro_rules! area_effect_list {
+    ($($effect_names:ident),+) => {
+        fn parse_area_effect(
+            effect_name: &str,
+            effect_parameters: &str,
+        ) -> Result<Box<dyn AreaEffect>, anyhow::Error> {
+            match effect_name {
+                $(stringify!($effect_names) => {
+                    Ok(Box::new(
+                            $effect_names::from_str(effect_parameters)
+                            .with_context(||
+                                format!("faied to parse {} (the correct format is {})", stringify!($effect_names), $effect_names::input_format())
+                            )?
+                    ))
+                }),+
+                _ => Err(GameSystemError::NotFoundAreaType(effect_name.to_owned()).into()),
+            }
+        }
+
+        /// 設定できる効果の名前、入力形式、既定のパラメータを持つ効果の一覧
+        pub fn area_effect_catalog() -> Vec<(&'static str, &'static str, Box<dyn AreaEffect>)> {
+            vec![$((
+                stringify!($effect_names),
+                $effect_names::input_format(),
+                Box::new($effect_names::default()) as Box<dyn AreaEffect>,
+            )),+]
+        }
+    };
+}
+
+area_effect_list!(
+    GoToStart,
+    SkipSelf,
+    PushSelf,
+    PushOthersAll,
+    PullSelf,
+    PullOthersAll,
+    RandomChase,
+    BoostDice,
+    OnceOnly,
+    ScaledPush,
+    WaitForPass,
+    Handicap,
+    BankRoll,
+    UseBank,
+    Mirror,
+    TimeStop,
+    ShowMap,
+    LeashToPack,
+    Confuse,
+    Veto,
+    Desperation,
+    Haunt,
+    SetFlag,
+    GotoLabelIfFlag
+);
+
 macro_rules! err_msg_wrong_parameter {
     ($key:expr) => {
         format!("{} is a wrong parameter", $key)
@@ -122,19 +461,22 @@ macro_rules! err_msg_parse_parameter {
     };
 }
 
+/// `key = value`のカンマ区切りのリストを読み込む
+///
+/// キーと値に含まれる空白は取り除かれる。
+/// 値をダブルクォートで囲んだ場合は、クォートの内側の空白やカンマ、コロンがそのまま残る。
 fn try_get_key_value_list(
     effect_parameters: &str,
 ) -> Result<HashMap<String, String>, anyhow::Error> {
     let mut key_value_list = HashMap::new();
-    for key_value_str in effect_parameters.split(',') {
-        let key_value_strings: Vec<_> = key_value_str.split('=').map(|s| s.to_owned()).collect();
-        if key_value_strings.len() != 2 {
-            return Err(anyhow!(
+    for key_value_str in split_outside_quotes(effect_parameters, ',')? {
+        let (key, value) = key_value_str.split_once('=').ok_or_else(|| {
+            anyhow!(
                 "failed to parse area effect parameters (the correct format is comma separated `key = value` list)."
-            ));
-        }
-        let key = key_value_strings.get(0).unwrap().to_owned();
-        let value = key_value_strings.get(1).unwrap().to_owned();
+            )
+        })?;
+        let key = remove_whitespace(key);
+        let value = try_unquote(value.trim())?;
         if key_value_list.contains_key(&key) {
             return Err(anyhow!(format!(
                 "failed to parse area effect parameters (`{}` is duplicated).",
@@ -146,6 +488,48 @@ fn try_get_key_value_list(
     Ok(key_value_list)
 }
 
+/// ダブルクォートの外側にある`delimiter`で文字列を分割する
+fn split_outside_quotes(text: &str, delimiter: char) -> Result<Vec<&str>, anyhow::Error> {
+    let mut token_list = Vec::new();
+    let mut token_start = 0;
+    let mut is_quoted = false;
+    for (i, c) in text.char_indices() {
+        if c == '"' {
+            is_quoted = !is_quoted;
+        } else if c == delimiter && !is_quoted {
+            token_list.push(&text[token_start..i]);
+            token_start = i + c.len_utf8();
+        }
+    }
+    if is_quoted {
+        return Err(anyhow!(
+            "failed to parse `{}` (the closing quote is missing).",
+            text
+        ));
+    }
+    token_list.push(&text[token_start..]);
+    Ok(token_list)
+}
+
+/// ダブルクォートで囲まれた値からクォートを取り除く（囲まれていない場合は空白を取り除いて返す）
+fn try_unquote(value: &str) -> Result<String, anyhow::Error> {
+    match value.strip_prefix('"') {
+        Some(quoted_value) => match quoted_value.strip_suffix('"') {
+            Some(unquoted_value) if !unquoted_value.contains('"') => Ok(unquoted_value.to_owned()),
+            _ => Err(anyhow!(
+                "failed to parse a quoted value `{}` (the closing quote is missing or misplaced).",
+                value
+            )),
+        },
+        None => Ok(remove_whitespace(value)),
+    }
+}
+
+/// 文字列に含まれる空白をすべて取り除く
+fn remove_whitespace(text: &str) -> String {
+    text.chars().filter(|c| !c.is_whitespace()).collect()
+}
+
 /// 何も起こらない
 #[derive(Clone, Debug)]
 pub struct NoEffect {}
@@ -163,12 +547,20 @@ impl AreaEffect for NoEffect {
             Language::Japanese => "なし".to_string(),
         }
     }
+    fn help_text(&self, preferences: &Preferences) -> String {
+        match preferences.language() {
+            Language::Japanese => "何も起こりません。".to_string(),
+        }
+    }
     fn execute(
         &self,
         _current_player: &str,
         _player_order: &[String],
         _player_status_list: &mut HashMap<String, PlayerStatus>,
-        _rng: &mut ThreadRng,
+        _rng: &mut dyn RngCore,
+        _flag_table: &mut HashMap<String, bool>,
+        _area_index: usize,
+        _goal_position: usize,
         _arguments: &str,
     ) -> Result<(), GameSystemError> {
         Ok(())
@@ -177,7 +569,7 @@ impl AreaEffect for NoEffect {
 
 /// 振り出しに戻る
 /// 入力形式は`GoToStart:`
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Default)]
 pub struct GoToStart {}
 impl GoToStart {
     fn new() -> Self {
@@ -200,17 +592,28 @@ impl AreaEffect for GoToStart {
     fn need_argument(&self) -> bool {
         false
     }
+    fn jump_target(&self, _area_index: usize, _goal_position: usize) -> Option<usize> {
+        Some(0)
+    }
     fn effect_text(&self, preferences: &Preferences) -> String {
         match preferences.language() {
             Language::Japanese => "振り出しに戻る。".to_string(),
         }
     }
+    fn help_text(&self, preferences: &Preferences) -> String {
+        match preferences.language() {
+            Language::Japanese => "止まったプレイヤーはスタートのマスに戻ります。".to_string(),
+        }
+    }
     fn execute(
         &self,
         current_player: &str,
         _player_order: &[String],
         player_status_table: &mut HashMap<String, PlayerStatus>,
-        _rng: &mut ThreadRng,
+        _rng: &mut dyn RngCore,
+        _flag_table: &mut HashMap<String, bool>,
+        _area_index: usize,
+        _goal_position: usize,
         _arguments: &str,
     ) -> Result<(), GameSystemError> {
         player_status_table
@@ -224,7 +627,7 @@ impl AreaEffect for GoToStart {
 /// 次回以降プレイヤーをスキップする
 ///
 /// 入力形式は`SkipSelf: times = <u8>`
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Default)]
 pub struct SkipSelf {
     num_skip: u8,
 }
@@ -265,12 +668,20 @@ impl AreaEffect for SkipSelf {
             Language::Japanese => format!("プレイヤーの休みを{}回追加。", self.num_skip),
         }
     }
+    fn help_text(&self, preferences: &Preferences) -> String {
+        match preferences.language() {
+            Language::Japanese => "止まったプレイヤーの休みの回数を`times`回追加します。休みの間はサイコロを振ることができません。".to_string(),
+        }
+    }
     fn execute(
         &self,
         current_player: &str,
         _player_order: &[String],
         player_status_table: &mut HashMap<String, PlayerStatus>,
-        _rng: &mut ThreadRng,
+        _rng: &mut dyn RngCore,
+        _flag_table: &mut HashMap<String, bool>,
+        _area_index: usize,
+        _goal_position: usize,
         _arguments: &str,
     ) -> Result<(), GameSystemError> {
         player_status_table
@@ -284,7 +695,7 @@ impl AreaEffect for SkipSelf {
 /// プレイヤーを進める
 ///
 /// 入力形式は `PushSelf: num = <usize>`
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Default)]
 pub struct PushSelf {
     num_step: usize,
 }
@@ -325,12 +736,23 @@ impl AreaEffect for PushSelf {
             Language::Japanese => format!("プレイヤーは{} マス進む。", self.num_step),
         }
     }
+    fn help_text(&self, preferences: &Preferences) -> String {
+        match preferences.language() {
+            Language::Japanese => {
+                "止まったプレイヤーを`num`マス進めます。ゴールを越える場合はゴールで止まります。"
+                    .to_string()
+            }
+        }
+    }
     fn execute(
         &self,
         current_player: &str,
         _player_order: &[String],
         player_status_table: &mut HashMap<String, PlayerStatus>,
-        _rng: &mut ThreadRng,
+        _rng: &mut dyn RngCore,
+        _flag_table: &mut HashMap<String, bool>,
+        _area_index: usize,
+        _goal_position: usize,
         _arguments: &str,
     ) -> Result<(), GameSystemError> {
         player_status_table
@@ -344,7 +766,7 @@ impl AreaEffect for PushSelf {
 /// 自分以外のプレイヤーを進める
 ///
 /// 入力形式は `PushOthersAll: num = <usize>`
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Default)]
 pub struct PushOthersAll {
     num_step: usize,
 }
@@ -385,12 +807,20 @@ impl AreaEffect for PushOthersAll {
             Language::Japanese => format!("自分以外のプレイヤーは{} マス進む。", self.num_step),
         }
     }
+    fn help_text(&self, preferences: &Preferences) -> String {
+        match preferences.language() {
+            Language::Japanese => "止まったプレイヤー以外の全員を`num`マス進めます。".to_string(),
+        }
+    }
     fn execute(
         &self,
         current_player: &str,
         player_order: &[String],
         player_status_table: &mut HashMap<String, PlayerStatus>,
-        _rng: &mut ThreadRng,
+        _rng: &mut dyn RngCore,
+        _flag_table: &mut HashMap<String, bool>,
+        _area_index: usize,
+        _goal_position: usize,
         _arguments: &str,
     ) -> Result<(), GameSystemError> {
         for player in player_order {
@@ -408,7 +838,7 @@ impl AreaEffect for PushOthersAll {
 /// プレイヤーを戻す
 ///
 /// 入力形式は `PullSelf: num = <usize>`
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Default)]
 pub struct PullSelf {
     num_step: usize,
 }
@@ -449,12 +879,22 @@ impl AreaEffect for PullSelf {
             Language::Japanese => format!("プレイヤーは{} マス戻る。", self.num_step),
         }
     }
+    fn help_text(&self, preferences: &Preferences) -> String {
+        match preferences.language() {
+            Language::Japanese => {
+                "止まったプレイヤーを`num`マス戻します。スタートより前には戻りません。".to_string()
+            }
+        }
+    }
     fn execute(
         &self,
         current_player: &str,
         _player_order: &[String],
         player_status_table: &mut HashMap<String, PlayerStatus>,
-        _rng: &mut ThreadRng,
+        _rng: &mut dyn RngCore,
+        _flag_table: &mut HashMap<String, bool>,
+        _area_index: usize,
+        _goal_position: usize,
         _arguments: &str,
     ) -> Result<(), GameSystemError> {
         player_status_table
@@ -468,7 +908,7 @@ impl AreaEffect for PullSelf {
 /// 自分以外のプレイヤーを戻す
 ///
 /// 入力形式は `PullOthersAll: num = <usize>`
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Default)]
 pub struct PullOthersAll {
     num_step: usize,
 }
@@ -509,12 +949,23 @@ impl AreaEffect for PullOthersAll {
             Language::Japanese => format!("自分以外のプレイヤーは{} マス戻す。", self.num_step),
         }
     }
+    fn help_text(&self, preferences: &Preferences) -> String {
+        match preferences.language() {
+            Language::Japanese => {
+                "止まったプレイヤー以外の全員を`num`マス戻します。スタートより前には戻りません。"
+                    .to_string()
+            }
+        }
+    }
     fn execute(
         &self,
         current_player: &str,
         player_order: &[String],
         player_status_table: &mut HashMap<String, PlayerStatus>,
-        _rng: &mut ThreadRng,
+        _rng: &mut dyn RngCore,
+        _flag_table: &mut HashMap<String, bool>,
+        _area_index: usize,
+        _goal_position: usize,
         _arguments: &str,
     ) -> Result<(), GameSystemError> {
         for player in player_order {
@@ -528,3 +979,1714 @@ impl AreaEffect for PullOthersAll {
         Ok(())
     }
 }
+
+/// ゴールしていない他のプレイヤーをランダムに選び、そのプレイヤーと同じマスへ移動する
+///
+/// 入力形式は `RandomChase:`
+#[derive(Clone, Debug, Default)]
+pub struct RandomChase {}
+impl RandomChase {
+    fn new() -> Self {
+        Self {}
+    }
+    fn input_format() -> &'static str {
+        "`RandomChase:`"
+    }
+}
+impl FromStr for RandomChase {
+    type Err = anyhow::Error;
+    fn from_str(effect_parameters: &str) -> Result<Self, Self::Err> {
+        if !effect_parameters.is_empty() {
+            return Err(anyhow!("parameters must not exist"));
+        }
+        Ok(Self::new())
+    }
+}
+impl AreaEffect for RandomChase {
+    fn need_argument(&self) -> bool {
+        false
+    }
+    fn effect_text(&self, preferences: &Preferences) -> String {
+        match preferences.language() {
+            Language::Japanese => {
+                "ゴールしていない他のプレイヤーをランダムに選び、そのプレイヤーのマスへ移動する。"
+                    .to_string()
+            }
+        }
+    }
+    fn help_text(&self, preferences: &Preferences) -> String {
+        match preferences.language() {
+            Language::Japanese => "ゴールしていない他のプレイヤーをランダムに一人選び、止まったプレイヤーをそのプレイヤーと同じマスへ移動させます。該当するプレイヤーがいない場合は何も起こりません。".to_string(),
+        }
+    }
+    fn execute(
+        &self,
+        current_player: &str,
+        player_order: &[String],
+        player_status_table: &mut HashMap<String, PlayerStatus>,
+        rng: &mut dyn RngCore,
+        _flag_table: &mut HashMap<String, bool>,
+        _area_index: usize,
+        _goal_position: usize,
+        _arguments: &str,
+    ) -> Result<(), GameSystemError> {
+        let mut opponent_list = Vec::with_capacity(player_order.len());
+        for player in player_order {
+            if player != current_player
+                && player_status_table
+                    .get(player)
+                    .ok_or_else(|| GameSystemError::NotFoundPlayer(player.to_owned()))?
+                    .order_of_arrival()
+                    .is_none()
+            {
+                opponent_list.push(player);
+            }
+        }
+        if let Some(target_player) = opponent_list.choose(rng) {
+            let target_position = player_status_table
+                .get(target_player.as_str())
+                .ok_or_else(|| GameSystemError::NotFoundPlayer(target_player.to_string()))?
+                .position();
+            player_status_table
+                .get_mut(current_player)
+                .ok_or_else(|| GameSystemError::NotFoundPlayer(current_player.to_owned()))?
+                .set_position(target_position);
+        }
+        Ok(())
+    }
+}
+
+/// 次回以降プレイヤーのサイコロの最大値を一定回数増やす
+///
+/// 入力形式は `BoostDice: delta = <usize>, turns = <u8>`
+#[derive(Clone, Debug, Default)]
+pub struct BoostDice {
+    delta: usize,
+    num_turn: u8,
+}
+impl BoostDice {
+    pub fn new(delta: usize, num_turn: u8) -> Self {
+        Self { delta, num_turn }
+    }
+    fn input_format() -> &'static str {
+        "`BoostDice: delta = <usize>, turns = <u8>`"
+    }
+}
+impl FromStr for BoostDice {
+    type Err = anyhow::Error;
+    fn from_str(effect_parameters: &str) -> Result<Self, Self::Err> {
+        let mut delta = 0;
+        let mut num_turn = 0;
+        let key_value_list = try_get_key_value_list(effect_parameters)?;
+        for (key, value) in key_value_list {
+            match key.as_str() {
+                "delta" => {
+                    delta = value
+                        .parse()
+                        .with_context(|| err_msg_parse_parameter!(key))?;
+                }
+                "turns" => {
+                    num_turn = value
+                        .parse()
+                        .with_context(|| err_msg_parse_parameter!(key))?;
+                }
+                _ => {
+                    return Err(anyhow!(err_msg_wrong_parameter!(key)));
+                }
+            }
+        }
+        Ok(Self::new(delta, num_turn))
+    }
+}
+impl AreaEffect for BoostDice {
+    fn need_argument(&self) -> bool {
+        false
+    }
+    fn effect_text(&self, preferences: &Preferences) -> String {
+        match preferences.language() {
+            Language::Japanese => format!(
+                "次回から{}回の間、プレイヤーのサイコロの最大値が{}増える。",
+                self.num_turn, self.delta
+            ),
+        }
+    }
+    fn help_text(&self, preferences: &Preferences) -> String {
+        match preferences.language() {
+            Language::Japanese => "止まったプレイヤーが次にサイコロを振るときから`turns`回の間、そのプレイヤーのサイコロの最大値を`delta`増やします。すでに増えている場合は上書きされます。".to_string(),
+        }
+    }
+    fn execute(
+        &self,
+        current_player: &str,
+        _player_order: &[String],
+        player_status_table: &mut HashMap<String, PlayerStatus>,
+        _rng: &mut dyn RngCore,
+        _flag_table: &mut HashMap<String, bool>,
+        _area_index: usize,
+        _goal_position: usize,
+        _arguments: &str,
+    ) -> Result<(), GameSystemError> {
+        player_status_table
+            .get_mut(current_player)
+            .ok_or_else(|| GameSystemError::NotFoundPlayer(current_player.to_owned()))?
+            .set_dice_boost(self.delta, self.num_turn);
+        Ok(())
+    }
+}
+
+/// 各プレイヤーが初めてこのマスに止まったときだけ、内側の効果を発動する
+///
+/// 入力形式は `OnceOnly: <effect>`
+#[derive(Debug)]
+pub struct OnceOnly {
+    effect: Box<dyn AreaEffect>,
+}
+impl OnceOnly {
+    pub fn new(effect: Box<dyn AreaEffect>) -> Self {
+        Self { effect }
+    }
+    fn input_format() -> &'static str {
+        "`OnceOnly: EffectName: [parameters...]`"
+    }
+}
+impl Default for OnceOnly {
+    fn default() -> Self {
+        Self::new(Box::new(NoEffect::new()))
+    }
+}
+impl FromStr for OnceOnly {
+    type Err = anyhow::Error;
+    fn from_str(effect_parameters: &str) -> Result<Self, Self::Err> {
+        Ok(Self::new(<_>::from_str(effect_parameters)?))
+    }
+}
+impl AreaEffect for OnceOnly {
+    fn need_argument(&self) -> bool {
+        self.effect.need_argument()
+    }
+    fn show_map(&self) -> bool {
+        self.effect.show_map()
+    }
+    fn is_haunt(&self) -> bool {
+        self.effect.is_haunt()
+    }
+    fn jump_target(&self, area_index: usize, goal_position: usize) -> Option<usize> {
+        self.effect.jump_target(area_index, goal_position)
+    }
+    fn resolve_label(
+        &mut self,
+        label_table: &HashMap<String, usize>,
+    ) -> Result<(), GameSystemError> {
+        self.effect.resolve_label(label_table)
+    }
+    fn effect_text(&self, preferences: &Preferences) -> String {
+        match preferences.language() {
+            Language::Japanese => format!(
+                "初めて止まったときのみ: {}",
+                self.effect.effect_text(preferences)
+            ),
+        }
+    }
+    fn help_text(&self, preferences: &Preferences) -> String {
+        match preferences.language() {
+            Language::Japanese => "各プレイヤーが初めてこのマスに止まったときだけ、後ろに書いた効果を発動します。二度目以降は何も起こりません。".to_string(),
+        }
+    }
+    fn execute(
+        &self,
+        current_player: &str,
+        player_order: &[String],
+        player_status_table: &mut HashMap<String, PlayerStatus>,
+        rng: &mut dyn RngCore,
+        flag_table: &mut HashMap<String, bool>,
+        area_index: usize,
+        goal_position: usize,
+        arguments: &str,
+    ) -> Result<(), GameSystemError> {
+        if player_status_table
+            .get(current_player)
+            .ok_or_else(|| GameSystemError::NotFoundPlayer(current_player.to_owned()))?
+            .has_visited(area_index)
+        {
+            return Ok(());
+        }
+        self.effect.execute(
+            current_player,
+            player_order,
+            player_status_table,
+            rng,
+            flag_table,
+            area_index,
+            goal_position,
+            arguments,
+        )
+    }
+}
+
+/// プレイヤーの人数に比例してプレイヤーを進める
+///
+/// 入力形式は `ScaledPush: per_player = <usize>`
+#[derive(Clone, Debug, Default)]
+pub struct ScaledPush {
+    num_step_per_player: usize,
+}
+impl ScaledPush {
+    pub fn new(num_step_per_player: usize) -> Self {
+        Self {
+            num_step_per_player,
+        }
+    }
+    fn input_format() -> &'static str {
+        "`ScaledPush: per_player = <usize>`"
+    }
+}
+impl FromStr for ScaledPush {
+    type Err = anyhow::Error;
+    fn from_str(effect_parameters: &str) -> Result<Self, Self::Err> {
+        let mut num_step_per_player = 0;
+        let key_value_list = try_get_key_value_list(effect_parameters)?;
+        for (key, value) in key_value_list {
+            match key.as_str() {
+                "per_player" => {
+                    num_step_per_player = value
+                        .parse()
+                        .with_context(|| err_msg_parse_parameter!(key))?;
+                }
+                _ => {
+                    return Err(anyhow!(err_msg_wrong_parameter!(key)));
+                }
+            }
+        }
+        Ok(Self::new(num_step_per_player))
+    }
+}
+impl AreaEffect for ScaledPush {
+    fn need_argument(&self) -> bool {
+        false
+    }
+    fn effect_text(&self, preferences: &Preferences) -> String {
+        match preferences.language() {
+            Language::Japanese => format!(
+                "プレイヤーは（人数 × {}）マス進む。",
+                self.num_step_per_player
+            ),
+        }
+    }
+    fn help_text(&self, preferences: &Preferences) -> String {
+        match preferences.language() {
+            Language::Japanese => "止まったプレイヤーを（参加人数 × `per_player`）マス進めます。ゴールしたプレイヤーも人数に含めます。".to_string(),
+        }
+    }
+    fn execute(
+        &self,
+        current_player: &str,
+        player_order: &[String],
+        player_status_table: &mut HashMap<String, PlayerStatus>,
+        _rng: &mut dyn RngCore,
+        _flag_table: &mut HashMap<String, bool>,
+        _area_index: usize,
+        _goal_position: usize,
+        _arguments: &str,
+    ) -> Result<(), GameSystemError> {
+        player_status_table
+            .get_mut(current_player)
+            .ok_or_else(|| GameSystemError::NotFoundPlayer(current_player.to_owned()))?
+            .go_forward(self.num_step_per_player.saturating_mul(player_order.len()));
+        Ok(())
+    }
+}
+
+/// 他のプレイヤーに追い越されるまでプレイヤーを動けなくする
+///
+/// 入力形式は `WaitForPass:`
+#[derive(Clone, Debug, Default)]
+pub struct WaitForPass {}
+impl WaitForPass {
+    fn new() -> Self {
+        Self {}
+    }
+    fn input_format() -> &'static str {
+        "`WaitForPass:`"
+    }
+}
+impl FromStr for WaitForPass {
+    type Err = anyhow::Error;
+    fn from_str(effect_parameters: &str) -> Result<Self, Self::Err> {
+        if !effect_parameters.is_empty() {
+            return Err(anyhow!("parameters must not exist"));
+        }
+        Ok(Self::new())
+    }
+}
+impl AreaEffect for WaitForPass {
+    fn need_argument(&self) -> bool {
+        false
+    }
+    fn effect_text(&self, preferences: &Preferences) -> String {
+        match preferences.language() {
+            Language::Japanese => "他のプレイヤーに追い越されるまで動けない。".to_string(),
+        }
+    }
+    fn help_text(&self, preferences: &Preferences) -> String {
+        match preferences.language() {
+            Language::Japanese => "止まったプレイヤーは、ゴールしていない他のプレイヤーに追い越されるまでサイコロを振っても進めません。他のプレイヤーが全員前にいる場合は、誰かが前に進んだ時点で動けるようになります。ゴールしていない他のプレイヤーがいなくなった場合も動けるようになります。動けなかった手番も、ターン数で切れる効果の一回分として数えます。".to_string(),
+        }
+    }
+    fn execute(
+        &self,
+        current_player: &str,
+        _player_order: &[String],
+        player_status_table: &mut HashMap<String, PlayerStatus>,
+        _rng: &mut dyn RngCore,
+        _flag_table: &mut HashMap<String, bool>,
+        _area_index: usize,
+        _goal_position: usize,
+        _arguments: &str,
+    ) -> Result<(), GameSystemError> {
+        player_status_table
+            .get_mut(current_player)
+            .ok_or_else(|| GameSystemError::NotFoundPlayer(current_player.to_owned()))?
+            .set_waiting_for_pass(true);
+        Ok(())
+    }
+}
+
+/// プレイヤーより前にいる、ゴールしていない他のプレイヤーの休みを1回追加する
+///
+/// 入力形式は `Handicap:`
+#[derive(Clone, Debug, Default)]
+pub struct Handicap {}
+impl Handicap {
+    fn new() -> Self {
+        Self {}
+    }
+    fn input_format() -> &'static str {
+        "`Handicap:`"
+    }
+}
+impl FromStr for Handicap {
+    type Err = anyhow::Error;
+    fn from_str(effect_parameters: &str) -> Result<Self, Self::Err> {
+        if !effect_parameters.is_empty() {
+            return Err(anyhow!("parameters must not exist"));
+        }
+        Ok(Self::new())
+    }
+}
+impl AreaEffect for Handicap {
+    fn need_argument(&self) -> bool {
+        false
+    }
+    fn effect_text(&self, preferences: &Preferences) -> String {
+        match preferences.language() {
+            Language::Japanese => "自分より前にいるプレイヤーの休みを1回追加。".to_string(),
+        }
+    }
+    fn help_text(&self, preferences: &Preferences) -> String {
+        match preferences.language() {
+            Language::Japanese => "止まったプレイヤーよりゴールに近い、ゴールしていない他のプレイヤー全員の休みを1回追加します。同じマスや後ろにいるプレイヤーは対象外です。".to_string(),
+        }
+    }
+    fn execute(
+        &self,
+        current_player: &str,
+        player_order: &[String],
+        player_status_table: &mut HashMap<String, PlayerStatus>,
+        _rng: &mut dyn RngCore,
+        _flag_table: &mut HashMap<String, bool>,
+        _area_index: usize,
+        _goal_position: usize,
+        _arguments: &str,
+    ) -> Result<(), GameSystemError> {
+        let current_player_position = player_status_table
+            .get(current_player)
+            .ok_or_else(|| GameSystemError::NotFoundPlayer(current_player.to_owned()))?
+            .position();
+        for player in player_order {
+            if player == current_player {
+                continue;
+            }
+            let player_status = player_status_table
+                .get_mut(player)
+                .ok_or_else(|| GameSystemError::NotFoundPlayer(player.to_owned()))?;
+            if player_status.order_of_arrival().is_none()
+                && player_status.position() > current_player_position
+            {
+                player_status.add_num_skip(1);
+            }
+        }
+        Ok(())
+    }
+}
+
+/// 直前に振ったサイコロの値を後で使うために預ける
+///
+/// 入力形式は `BankRoll:`
+#[derive(Clone, Debug, Default)]
+pub struct BankRoll {}
+impl BankRoll {
+    fn new() -> Self {
+        Self {}
+    }
+    fn input_format() -> &'static str {
+        "`BankRoll:`"
+    }
+}
+impl FromStr for BankRoll {
+    type Err = anyhow::Error;
+    fn from_str(effect_parameters: &str) -> Result<Self, Self::Err> {
+        if !effect_parameters.is_empty() {
+            return Err(anyhow!("parameters must not exist"));
+        }
+        Ok(Self::new())
+    }
+}
+impl AreaEffect for BankRoll {
+    fn need_argument(&self) -> bool {
+        false
+    }
+    fn effect_text(&self, preferences: &Preferences) -> String {
+        match preferences.language() {
+            Language::Japanese => "今回のサイコロの値を預ける。".to_string(),
+        }
+    }
+    fn help_text(&self, preferences: &Preferences) -> String {
+        match preferences.language() {
+            Language::Japanese => "止まったプレイヤーが今回振ったサイコロの値を預けます。預けた値は`UseBank`のマスで使えます。預けられる値は一つだけで、すでに預けている場合は何も起こりません。".to_string(),
+        }
+    }
+    fn execute(
+        &self,
+        current_player: &str,
+        _player_order: &[String],
+        player_status_table: &mut HashMap<String, PlayerStatus>,
+        _rng: &mut dyn RngCore,
+        _flag_table: &mut HashMap<String, bool>,
+        _area_index: usize,
+        _goal_position: usize,
+        _arguments: &str,
+    ) -> Result<(), GameSystemError> {
+        let current_player_status = player_status_table
+            .get_mut(current_player)
+            .ok_or_else(|| GameSystemError::NotFoundPlayer(current_player.to_owned()))?;
+        if let Some(dice) = current_player_status.last_dice() {
+            current_player_status.bank_dice(dice);
+        }
+        Ok(())
+    }
+}
+
+/// 預けてあるサイコロの値だけプレイヤーを進める
+///
+/// 入力形式は `UseBank:`
+#[derive(Clone, Debug, Default)]
+pub struct UseBank {}
+impl UseBank {
+    fn new() -> Self {
+        Self {}
+    }
+    fn input_format() -> &'static str {
+        "`UseBank:`"
+    }
+}
+impl FromStr for UseBank {
+    type Err = anyhow::Error;
+    fn from_str(effect_parameters: &str) -> Result<Self, Self::Err> {
+        if !effect_parameters.is_empty() {
+            return Err(anyhow!("parameters must not exist"));
+        }
+        Ok(Self::new())
+    }
+}
+impl AreaEffect for UseBank {
+    fn need_argument(&self) -> bool {
+        false
+    }
+    fn effect_text(&self, preferences: &Preferences) -> String {
+        match preferences.language() {
+            Language::Japanese => "預けたサイコロの値だけ進む。".to_string(),
+        }
+    }
+    fn help_text(&self, preferences: &Preferences) -> String {
+        match preferences.language() {
+            Language::Japanese => "止まったプレイヤーが`BankRoll`で預けたサイコロの値だけ進めます。使った値はなくなります。預けていない場合は何も起こりません。".to_string(),
+        }
+    }
+    fn execute(
+        &self,
+        current_player: &str,
+        _player_order: &[String],
+        player_status_table: &mut HashMap<String, PlayerStatus>,
+        _rng: &mut dyn RngCore,
+        _flag_table: &mut HashMap<String, bool>,
+        _area_index: usize,
+        _goal_position: usize,
+        _arguments: &str,
+    ) -> Result<(), GameSystemError> {
+        let current_player_status = player_status_table
+            .get_mut(current_player)
+            .ok_or_else(|| GameSystemError::NotFoundPlayer(current_player.to_owned()))?;
+        if let Some(dice) = current_player_status.take_banked_dice() {
+            current_player_status.go_forward(dice);
+        }
+        Ok(())
+    }
+}
+
+/// プレイヤーの位置を盤面の中央を軸に反転させる
+///
+/// 入力形式は `Mirror:`
+#[derive(Clone, Debug, Default)]
+pub struct Mirror {}
+impl Mirror {
+    fn new() -> Self {
+        Self {}
+    }
+    fn input_format() -> &'static str {
+        "`Mirror:`"
+    }
+}
+impl FromStr for Mirror {
+    type Err = anyhow::Error;
+    fn from_str(effect_parameters: &str) -> Result<Self, Self::Err> {
+        if !effect_parameters.is_empty() {
+            return Err(anyhow!("parameters must not exist"));
+        }
+        Ok(Self::new())
+    }
+}
+impl AreaEffect for Mirror {
+    fn need_argument(&self) -> bool {
+        false
+    }
+    fn jump_target(&self, area_index: usize, goal_position: usize) -> Option<usize> {
+        Some(goal_position - area_index.min(goal_position))
+    }
+    fn effect_text(&self, preferences: &Preferences) -> String {
+        match preferences.language() {
+            Language::Japanese => "盤面の反対側へ移動する。".to_string(),
+        }
+    }
+    fn help_text(&self, preferences: &Preferences) -> String {
+        match preferences.language() {
+            Language::Japanese => "止まったプレイヤーは盤面の中央を軸に反転した位置へ移動します。ゴールから k マス手前にいればスタートから k マス目に移ります。".to_string(),
+        }
+    }
+    fn execute(
+        &self,
+        current_player: &str,
+        _player_order: &[String],
+        player_status_table: &mut HashMap<String, PlayerStatus>,
+        _rng: &mut dyn RngCore,
+        _flag_table: &mut HashMap<String, bool>,
+        _area_index: usize,
+        goal_position: usize,
+        _arguments: &str,
+    ) -> Result<(), GameSystemError> {
+        let current_player_status = player_status_table
+            .get_mut(current_player)
+            .ok_or_else(|| GameSystemError::NotFoundPlayer(current_player.to_owned()))?;
+        let position = current_player_status.position().min(goal_position);
+        current_player_status.set_position(goal_position - position);
+        Ok(())
+    }
+}
+
+/// 他のプレイヤーを飛ばして続けて手番を行う
+///
+/// 入力形式は `TimeStop: turns = <u8>`
+#[derive(Clone, Debug, Default)]
+pub struct TimeStop {
+    num_extra_turn: u8,
+}
+impl TimeStop {
+    fn new(num_extra_turn: u8) -> Self {
+        Self { num_extra_turn }
+    }
+    fn input_format() -> &'static str {
+        "`TimeStop: turns = <u8>`"
+    }
+}
+impl FromStr for TimeStop {
+    type Err = anyhow::Error;
+    fn from_str(effect_parameters: &str) -> Result<Self, Self::Err> {
+        let mut num_extra_turn = 0;
+        let key_value_list = try_get_key_value_list(effect_parameters)?;
+        for (key, value) in key_value_list {
+            match key.as_str() {
+                "turns" => {
+                    num_extra_turn = value
+                        .parse()
+                        .with_context(|| err_msg_parse_parameter!(key))?;
+                }
+                _ => {
+                    return Err(anyhow!(err_msg_wrong_parameter!(key)));
+                }
+            }
+        }
+        Ok(Self::new(num_extra_turn))
+    }
+}
+impl AreaEffect for TimeStop {
+    fn need_argument(&self) -> bool {
+        false
+    }
+    fn effect_text(&self, preferences: &Preferences) -> String {
+        match preferences.language() {
+            Language::Japanese => format!(
+                "時が止まり、続けて{}回サイコロを振る。",
+                self.num_extra_turn
+            ),
+        }
+    }
+    fn help_text(&self, preferences: &Preferences) -> String {
+        match preferences.language() {
+            Language::Japanese => "止まったプレイヤーは他のプレイヤーを飛ばして、続けて`turns`回手番を行います。飛ばされたプレイヤーの休みの回数は減りません。".to_string(),
+        }
+    }
+    fn execute(
+        &self,
+        current_player: &str,
+        _player_order: &[String],
+        player_status_table: &mut HashMap<String, PlayerStatus>,
+        _rng: &mut dyn RngCore,
+        _flag_table: &mut HashMap<String, bool>,
+        _area_index: usize,
+        _goal_position: usize,
+        _arguments: &str,
+    ) -> Result<(), GameSystemError> {
+        player_status_table
+            .get_mut(current_player)
+            .ok_or_else(|| GameSystemError::NotFoundPlayer(current_player.to_owned()))?
+            .add_num_extra_turn(self.num_extra_turn);
+        Ok(())
+    }
+}
+
+/// ゴールまでの先のマスの一覧を表示する
+///
+/// 入力形式は `ShowMap:`
+#[derive(Clone, Debug, Default)]
+pub struct ShowMap {}
+impl ShowMap {
+    fn new() -> Self {
+        Self {}
+    }
+    fn input_format() -> &'static str {
+        "`ShowMap:`"
+    }
+}
+impl FromStr for ShowMap {
+    type Err = anyhow::Error;
+    fn from_str(effect_parameters: &str) -> Result<Self, Self::Err> {
+        if !effect_parameters.is_empty() {
+            return Err(anyhow!("parameters must not exist"));
+        }
+        Ok(Self::new())
+    }
+}
+impl AreaEffect for ShowMap {
+    fn need_argument(&self) -> bool {
+        false
+    }
+    fn effect_text(&self, preferences: &Preferences) -> String {
+        match preferences.language() {
+            Language::Japanese => "ゴールまでの地図を見る。".to_string(),
+        }
+    }
+    fn help_text(&self, preferences: &Preferences) -> String {
+        match preferences.language() {
+            Language::Japanese => "止まったプレイヤーの先にあるゴールまでのマスと、その効果を一覧で表示します。プレイヤーの状態は変化しません。".to_string(),
+        }
+    }
+    fn show_map(&self) -> bool {
+        true
+    }
+    fn execute(
+        &self,
+        _current_player: &str,
+        _player_order: &[String],
+        _player_status_table: &mut HashMap<String, PlayerStatus>,
+        _rng: &mut dyn RngCore,
+        _flag_table: &mut HashMap<String, bool>,
+        _area_index: usize,
+        _goal_position: usize,
+        _arguments: &str,
+    ) -> Result<(), GameSystemError> {
+        Ok(())
+    }
+}
+
+/// 他のプレイヤーより離れすぎないようにプレイヤーを戻す
+///
+/// 入力形式は `LeashToPack: max_lead = <usize>`
+#[derive(Clone, Debug, Default)]
+pub struct LeashToPack {
+    max_lead: usize,
+}
+impl LeashToPack {
+    fn new(max_lead: usize) -> Self {
+        Self { max_lead }
+    }
+    fn input_format() -> &'static str {
+        "`LeashToPack: max_lead = <usize>`"
+    }
+}
+impl FromStr for LeashToPack {
+    type Err = anyhow::Error;
+    fn from_str(effect_parameters: &str) -> Result<Self, Self::Err> {
+        let mut max_lead = 0;
+        let key_value_list = try_get_key_value_list(effect_parameters)?;
+        for (key, value) in key_value_list {
+            match key.as_str() {
+                "max_lead" => {
+                    max_lead = value
+                        .parse()
+                        .with_context(|| err_msg_parse_parameter!(key))?;
+                }
+                _ => {
+                    return Err(anyhow!(err_msg_wrong_parameter!(key)));
+                }
+            }
+        }
+        Ok(Self::new(max_lead))
+    }
+}
+impl AreaEffect for LeashToPack {
+    fn need_argument(&self) -> bool {
+        false
+    }
+    fn effect_text(&self, preferences: &Preferences) -> String {
+        match preferences.language() {
+            Language::Japanese => format!(
+                "他のプレイヤーより{}マスを超えて先にいる場合は{}マス差まで戻る。",
+                self.max_lead, self.max_lead
+            ),
+        }
+    }
+    fn help_text(&self, preferences: &Preferences) -> String {
+        match preferences.language() {
+            Language::Japanese => "止まったプレイヤーが、ゴールしていない他のプレイヤーの中で最も進んでいるプレイヤーより`max_lead`マスを超えて先にいる場合、ちょうど`max_lead`マス先の位置まで戻します。".to_string(),
+        }
+    }
+    fn execute(
+        &self,
+        current_player: &str,
+        player_order: &[String],
+        player_status_table: &mut HashMap<String, PlayerStatus>,
+        _rng: &mut dyn RngCore,
+        _flag_table: &mut HashMap<String, bool>,
+        _area_index: usize,
+        _goal_position: usize,
+        _arguments: &str,
+    ) -> Result<(), GameSystemError> {
+        let mut leading_opponent_position = None;
+        for player in player_order {
+            if player == current_player {
+                continue;
+            }
+            let player_status = player_status_table
+                .get(player)
+                .ok_or_else(|| GameSystemError::NotFoundPlayer(player.to_owned()))?;
+            if player_status.order_of_arrival().is_none() {
+                leading_opponent_position =
+                    leading_opponent_position.max(Some(player_status.position()));
+            }
+        }
+        if let Some(leading_opponent_position) = leading_opponent_position {
+            let current_player_status = player_status_table
+                .get_mut(current_player)
+                .ok_or_else(|| GameSystemError::NotFoundPlayer(current_player.to_owned()))?;
+            let max_position = leading_opponent_position.saturating_add(self.max_lead);
+            if current_player_status.position() > max_position {
+                current_player_status.set_position(max_position);
+            }
+        }
+        Ok(())
+    }
+}
+
+/// 次回以降プレイヤーをサイコロの目だけ戻らせる
+///
+/// 入力形式は `Confuse: turns = <u8>`
+#[derive(Clone, Debug, Default)]
+pub struct Confuse {
+    num_confusion: u8,
+}
+impl Confuse {
+    fn new(num_confusion: u8) -> Self {
+        Self { num_confusion }
+    }
+    fn input_format() -> &'static str {
+        "`Confuse: turns = <u8>`"
+    }
+}
+impl FromStr for Confuse {
+    type Err = anyhow::Error;
+    fn from_str(effect_parameters: &str) -> Result<Self, Self::Err> {
+        let mut num_confusion = 0;
+        let key_value_list = try_get_key_value_list(effect_parameters)?;
+        for (key, value) in key_value_list {
+            match key.as_str() {
+                "turns" => {
+                    num_confusion = value
+                        .parse()
+                        .with_context(|| err_msg_parse_parameter!(key))?;
+                }
+                _ => {
+                    return Err(anyhow!(err_msg_wrong_parameter!(key)));
+                }
+            }
+        }
+        Ok(Self::new(num_confusion))
+    }
+}
+impl AreaEffect for Confuse {
+    fn need_argument(&self) -> bool {
+        false
+    }
+    fn effect_text(&self, preferences: &Preferences) -> String {
+        match preferences.language() {
+            Language::Japanese => format!(
+                "混乱して次回から{}回、サイコロの目だけ戻る。",
+                self.num_confusion
+            ),
+        }
+    }
+    fn help_text(&self, preferences: &Preferences) -> String {
+        match preferences.language() {
+            Language::Japanese => "止まったプレイヤーは次の手番から`turns`回の間、サイコロの目だけ前に進む代わりに後ろへ戻ります。スタートより前には戻りません。".to_string(),
+        }
+    }
+    fn execute(
+        &self,
+        current_player: &str,
+        _player_order: &[String],
+        player_status_table: &mut HashMap<String, PlayerStatus>,
+        _rng: &mut dyn RngCore,
+        _flag_table: &mut HashMap<String, bool>,
+        _area_index: usize,
+        _goal_position: usize,
+        _arguments: &str,
+    ) -> Result<(), GameSystemError> {
+        player_status_table
+            .get_mut(current_player)
+            .ok_or_else(|| GameSystemError::NotFoundPlayer(current_player.to_owned()))?
+            .add_num_confusion(self.num_confusion);
+        Ok(())
+    }
+}
+
+/// 次に自分が対象となる効果を取り消す拒否権を得る
+///
+/// 入力形式は `Veto:`
+#[derive(Clone, Debug, Default)]
+pub struct Veto {}
+impl Veto {
+    fn new() -> Self {
+        Self {}
+    }
+    fn input_format() -> &'static str {
+        "`Veto:`"
+    }
+}
+impl FromStr for Veto {
+    type Err = anyhow::Error;
+    fn from_str(effect_parameters: &str) -> Result<Self, Self::Err> {
+        if !effect_parameters.is_empty() {
+            return Err(anyhow!("parameters must not exist"));
+        }
+        Ok(Self::new())
+    }
+}
+impl AreaEffect for Veto {
+    fn need_argument(&self) -> bool {
+        false
+    }
+    fn effect_text(&self, preferences: &Preferences) -> String {
+        match preferences.language() {
+            Language::Japanese => "拒否権を得る。".to_string(),
+        }
+    }
+    fn help_text(&self, preferences: &Preferences) -> String {
+        match preferences.language() {
+            Language::Japanese => "止まったプレイヤーは拒否権を得ます（一つまで）。次にマスの効果で位置や休みの回数、サイコロの状態などが変わるとき、拒否権を消費してその効果を取り消します。良い効果も取り消されます。".to_string(),
+        }
+    }
+    fn execute(
+        &self,
+        current_player: &str,
+        _player_order: &[String],
+        player_status_table: &mut HashMap<String, PlayerStatus>,
+        _rng: &mut dyn RngCore,
+        _flag_table: &mut HashMap<String, bool>,
+        _area_index: usize,
+        _goal_position: usize,
+        _arguments: &str,
+    ) -> Result<(), GameSystemError> {
+        player_status_table
+            .get_mut(current_player)
+            .ok_or_else(|| GameSystemError::NotFoundPlayer(current_player.to_owned()))?
+            .set_veto(true);
+        Ok(())
+    }
+}
+
+/// 最後尾にいる間だけサイコロの最大値を1増やす
+///
+/// 入力形式は `Desperation:`
+#[derive(Clone, Debug, Default)]
+pub struct Desperation {}
+impl Desperation {
+    fn new() -> Self {
+        Self {}
+    }
+    fn input_format() -> &'static str {
+        "`Desperation:`"
+    }
+}
+impl FromStr for Desperation {
+    type Err = anyhow::Error;
+    fn from_str(effect_parameters: &str) -> Result<Self, Self::Err> {
+        if !effect_parameters.is_empty() {
+            return Err(anyhow!("parameters must not exist"));
+        }
+        Ok(Self::new())
+    }
+}
+impl AreaEffect for Desperation {
+    fn need_argument(&self) -> bool {
+        false
+    }
+    fn effect_text(&self, preferences: &Preferences) -> String {
+        match preferences.language() {
+            Language::Japanese => "以降、最後尾にいる間はサイコロの最大値が1増える。".to_string(),
+        }
+    }
+    fn help_text(&self, preferences: &Preferences) -> String {
+        match preferences.language() {
+            Language::Japanese => "止まったプレイヤーは以降、ゴールしていないプレイヤーの中で最も後ろにいる間だけサイコロの最大値が1増えます。最後尾かどうかはサイコロを振るたびに判定され、追い抜くと増加はなくなります。".to_string(),
+        }
+    }
+    fn execute(
+        &self,
+        current_player: &str,
+        _player_order: &[String],
+        player_status_table: &mut HashMap<String, PlayerStatus>,
+        _rng: &mut dyn RngCore,
+        _flag_table: &mut HashMap<String, bool>,
+        _area_index: usize,
+        _goal_position: usize,
+        _arguments: &str,
+    ) -> Result<(), GameSystemError> {
+        player_status_table
+            .get_mut(current_player)
+            .ok_or_else(|| GameSystemError::NotFoundPlayer(current_player.to_owned()))?
+            .set_desperate(true);
+        Ok(())
+    }
+}
+
+/// 以前にこのマスに止まったプレイヤーにも、このマスの他の効果を発動させる
+///
+/// 入力形式は `Haunt:`
+#[derive(Clone, Debug, Default)]
+pub struct Haunt {}
+impl Haunt {
+    fn new() -> Self {
+        Self {}
+    }
+    fn input_format() -> &'static str {
+        "`Haunt:`"
+    }
+}
+impl FromStr for Haunt {
+    type Err = anyhow::Error;
+    fn from_str(effect_parameters: &str) -> Result<Self, Self::Err> {
+        if !effect_parameters.is_empty() {
+            return Err(anyhow!("parameters must not exist"));
+        }
+        Ok(Self::new())
+    }
+}
+impl AreaEffect for Haunt {
+    fn need_argument(&self) -> bool {
+        false
+    }
+    fn effect_text(&self, preferences: &Preferences) -> String {
+        match preferences.language() {
+            Language::Japanese => "前にここへ止まったプレイヤーも同じ目にあう。".to_string(),
+        }
+    }
+    fn help_text(&self, preferences: &Preferences) -> String {
+        match preferences.language() {
+            Language::Japanese => "このマスに最後に止まったプレイヤーを覚えておきます。次に別のプレイヤーが止まると、覚えていたプレイヤーにもその場でこのマスの他の効果が発動します。覚えていたプレイヤーがゴールしている場合は何も起こりません。".to_string(),
+        }
+    }
+    fn is_haunt(&self) -> bool {
+        true
+    }
+    fn execute(
+        &self,
+        current_player: &str,
+        player_order: &[String],
+        player_status_table: &mut HashMap<String, PlayerStatus>,
+        _rng: &mut dyn RngCore,
+        _flag_table: &mut HashMap<String, bool>,
+        area_index: usize,
+        _goal_position: usize,
+        _arguments: &str,
+    ) -> Result<(), GameSystemError> {
+        for player in player_order {
+            player_status_table
+                .get_mut(player)
+                .ok_or_else(|| GameSystemError::NotFoundPlayer(player.to_owned()))?
+                .set_haunting(area_index, player == current_player);
+        }
+        Ok(())
+    }
+}
+
+/// 盤面全体で共有する名前付きのフラグを立てる
+///
+/// 入力形式は `SetFlag: name = "<String>"`
+#[derive(Clone, Debug, Default)]
+pub struct SetFlag {
+    name: String,
+}
+impl SetFlag {
+    pub fn new(name: String) -> Self {
+        Self { name }
+    }
+    fn input_format() -> &'static str {
+        "`SetFlag: name = \"<String>\"`"
+    }
+}
+impl FromStr for SetFlag {
+    type Err = anyhow::Error;
+    fn from_str(effect_parameters: &str) -> Result<Self, Self::Err> {
+        let mut name = None;
+        let key_value_list = try_get_key_value_list(effect_parameters)?;
+        for (key, value) in key_value_list {
+            match key.as_str() {
+                "name" => {
+                    name = Some(value);
+                }
+                _ => {
+                    return Err(anyhow!(err_msg_wrong_parameter!(key)));
+                }
+            }
+        }
+        Ok(Self::new(
+            name.ok_or_else(|| anyhow!("a parameter `name` is missing"))?,
+        ))
+    }
+}
+impl AreaEffect for SetFlag {
+    fn need_argument(&self) -> bool {
+        false
+    }
+    fn effect_text(&self, preferences: &Preferences) -> String {
+        match preferences.language() {
+            Language::Japanese => format!("「{}」が有効になる。", self.name),
+        }
+    }
+    fn help_text(&self, preferences: &Preferences) -> String {
+        match preferences.language() {
+            Language::Japanese => "止まると盤面全体で共有するフラグ`name`を立てます。立てたフラグはゲームが終わるまで残り、`GotoLabelIfFlag`の条件に使えます。".to_string(),
+        }
+    }
+    fn execute(
+        &self,
+        _current_player: &str,
+        _player_order: &[String],
+        _player_status_table: &mut HashMap<String, PlayerStatus>,
+        _rng: &mut dyn RngCore,
+        flag_table: &mut HashMap<String, bool>,
+        _area_index: usize,
+        _goal_position: usize,
+        _arguments: &str,
+    ) -> Result<(), GameSystemError> {
+        flag_table.insert(self.name.clone(), true);
+        Ok(())
+    }
+}
+
+/// フラグが立っている場合、ラベルの付いたマスへプレイヤーを移動させる
+///
+/// 入力形式は `GotoLabelIfFlag: flag = "<String>", label = "<String>"`
+#[derive(Clone, Debug, Default)]
+pub struct GotoLabelIfFlag {
+    flag: String,
+    label: String,
+    /// 盤面を作り終えた後に解決される、ラベルの付いたマスの番号
+    target_position: Option<usize>,
+}
+impl GotoLabelIfFlag {
+    pub fn new(flag: String, label: String) -> Self {
+        Self {
+            flag,
+            label,
+            target_position: None,
+        }
+    }
+    fn input_format() -> &'static str {
+        "`GotoLabelIfFlag: flag = \"<String>\", label = \"<String>\"`"
+    }
+}
+impl FromStr for GotoLabelIfFlag {
+    type Err = anyhow::Error;
+    fn from_str(effect_parameters: &str) -> Result<Self, Self::Err> {
+        let mut flag = None;
+        let mut label = None;
+        let key_value_list = try_get_key_value_list(effect_parameters)?;
+        for (key, value) in key_value_list {
+            match key.as_str() {
+                "flag" => {
+                    flag = Some(value);
+                }
+                "label" => {
+                    label = Some(value);
+                }
+                _ => {
+                    return Err(anyhow!(err_msg_wrong_parameter!(key)));
+                }
+            }
+        }
+        Ok(Self::new(
+            flag.ok_or_else(|| anyhow!("a parameter `flag` is missing"))?,
+            label.ok_or_else(|| anyhow!("a parameter `label` is missing"))?,
+        ))
+    }
+}
+impl AreaEffect for GotoLabelIfFlag {
+    fn need_argument(&self) -> bool {
+        false
+    }
+    fn resolve_label(
+        &mut self,
+        label_table: &HashMap<String, usize>,
+    ) -> Result<(), GameSystemError> {
+        self.target_position = Some(
+            *label_table
+                .get(&self.label)
+                .ok_or_else(|| GameSystemError::NotFoundLabel(self.label.clone()))?,
+        );
+        Ok(())
+    }
+    fn effect_text(&self, preferences: &Preferences) -> String {
+        match preferences.language() {
+            Language::Japanese => format!("「{}」が有効なら「{}」へ移動。", self.flag, self.label),
+        }
+    }
+    fn help_text(&self, preferences: &Preferences) -> String {
+        match preferences.language() {
+            Language::Japanese => "`SetFlag`でフラグ`flag`が立っている場合、止まったプレイヤーを`label`の付いたマスへ移動させます。フラグが立っていない場合は何も起こりません。".to_string(),
+        }
+    }
+    fn execute(
+        &self,
+        current_player: &str,
+        _player_order: &[String],
+        player_status_table: &mut HashMap<String, PlayerStatus>,
+        _rng: &mut dyn RngCore,
+        flag_table: &mut HashMap<String, bool>,
+        _area_index: usize,
+        _goal_position: usize,
+        _arguments: &str,
+    ) -> Result<(), GameSystemError> {
+        if !flag_table.get(&self.flag).copied().unwrap_or(false) {
+            return Ok(());
+        }
+        let target_position = self
+            .target_position
+            .ok_or_else(|| GameSystemError::NotFoundLabel(self.label.clone()))?;
+        player_status_table
+            .get_mut(current_player)
+            .ok_or_else(|| GameSystemError::NotFoundPlayer(current_player.to_owned()))?
+            .set_position(target_position);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game_system::player_status::{
+        diff_player_status_table, player_status_table_from_position_list,
+    };
+
+    fn area_from_effect_list(effect_str_list: &[&str]) -> Area {
+        Area::new(
+            "テスト".to_string(),
+            effect_str_list
+                .iter()
+                .map(|effect_str| effect_str.parse().unwrap())
+                .collect(),
+        )
+    }
+
+    /// テストで使う盤面のゴールの位置
+    const GOAL_POSITION: usize = 10;
+
+    /// `current_player`が`area_index`番目の`area`に止まったときの状態の変化（訪問済みのマスの記録は除く）
+    fn diff_after_execute(
+        area: &Area,
+        current_player: &str,
+        player_order: &[String],
+        player_status_table: &mut HashMap<String, PlayerStatus>,
+        area_index: usize,
+        seed: u64,
+    ) -> Vec<String> {
+        let before = player_status_table.clone();
+        area.execute(
+            current_player,
+            player_order,
+            player_status_table,
+            &mut StdRng::seed_from_u64(seed),
+            &mut HashMap::new(),
+            area_index,
+            GOAL_POSITION,
+        )
+        .unwrap();
+        diff_player_status_table(&before, player_status_table)
+            .into_iter()
+            .filter(|diff| !diff.contains(".visited_area_set:"))
+            .collect()
+    }
+
+    #[test]
+    fn random_chase_moves_to_unfinished_opponent() {
+        let area = area_from_effect_list(&["RandomChase:"]);
+        let mut reached_position_list = Vec::new();
+        for seed in 0..20 {
+            let (player_order, mut player_status_table) =
+                player_status_table_from_position_list(&[
+                    ("Alice", 2),
+                    ("Bob", 5),
+                    ("Carol", 7),
+                    ("Dave", 9),
+                ]);
+            player_status_table
+                .get_mut("Carol")
+                .unwrap()
+                .set_order_of_arrival(1);
+            diff_after_execute(
+                &area,
+                "Alice",
+                &player_order,
+                &mut player_status_table,
+                2,
+                seed,
+            );
+            let position = player_status_table["Alice"].position();
+            assert!(position == 5 || position == 9, "{}", position);
+            reached_position_list.push(position);
+        }
+        assert!(reached_position_list.contains(&5) && reached_position_list.contains(&9));
+    }
+
+    #[test]
+    fn random_chase_is_reproducible_with_seed() {
+        let area = area_from_effect_list(&["RandomChase:"]);
+        let diff_list: Vec<_> = (0..2)
+            .map(|_| {
+                let (player_order, mut player_status_table) =
+                    player_status_table_from_position_list(&[
+                        ("Alice", 2),
+                        ("Bob", 5),
+                        ("Dave", 9),
+                    ]);
+                diff_after_execute(
+                    &area,
+                    "Alice",
+                    &player_order,
+                    &mut player_status_table,
+                    2,
+                    42,
+                )
+            })
+            .collect();
+        assert_eq!(diff_list[0], diff_list[1]);
+        assert_eq!(diff_list[0].len(), 1);
+    }
+
+    #[test]
+    fn preview_push_self_reports_destination_without_moving() {
+        let area = area_from_effect_list(&["PushSelf: num = 2"]);
+        let (player_order, player_status_table) =
+            player_status_table_from_position_list(&[("Alice", 3), ("Bob", 1)]);
+        let before = player_status_table.clone();
+        let preview_list = area
+            .preview(
+                &Preferences::default(),
+                "Alice",
+                &player_order,
+                &player_status_table,
+                &HashMap::new(),
+                3,
+                10,
+            )
+            .unwrap();
+        assert_eq!(
+            preview_list,
+            vec!["Alice: 3 マス目から 5 マス目へ移動する。"]
+        );
+        assert!(diff_player_status_table(&before, &player_status_table).is_empty());
+    }
+
+    #[test]
+    fn parse_area_effect_ignores_whitespace_outside_quotes() {
+        let area_effect: Box<dyn AreaEffect> = " Push Self : n um = 1 0 ".parse().unwrap();
+        assert_eq!(
+            area_effect.effect_text(&Preferences::default()),
+            "プレイヤーは10 マス進む。"
+        );
+    }
+
+    #[test]
+    fn key_value_list_trims_keys() {
+        let key_value_list = try_get_key_value_list("  delta =2,\tturns= 3 ").unwrap();
+        assert_eq!(
+            key_value_list,
+            HashMap::from([
+                ("delta".to_string(), "2".to_string()),
+                ("turns".to_string(), "3".to_string()),
+            ])
+        );
+    }
+
+    #[test]
+    fn key_value_list_keeps_quoted_spaces_commas_and_colons() {
+        let key_value_list =
+            try_get_key_value_list(r#"name = "Dragon Cave, 2F: entrance" , num = 3"#).unwrap();
+        assert_eq!(
+            key_value_list,
+            HashMap::from([
+                ("name".to_string(), "Dragon Cave, 2F: entrance".to_string()),
+                ("num".to_string(), "3".to_string()),
+            ])
+        );
+    }
+
+    #[test]
+    fn split_outside_quotes_ignores_quoted_delimiters() {
+        assert_eq!(
+            split_outside_quotes(r#"a = "x, y", b = 1"#, ',').unwrap(),
+            vec![r#"a = "x, y""#, " b = 1"]
+        );
+        assert!(split_outside_quotes(r#"a = "x, b = 1"#, ',').is_err());
+    }
+
+    #[test]
+    fn try_unquote_strips_only_matching_quotes() {
+        assert_eq!(try_unquote(r#""a b""#).unwrap(), "a b");
+        assert_eq!(try_unquote("a b").unwrap(), "ab");
+        assert!(try_unquote(r#""a"b""#).is_err());
+        assert!(try_unquote(r#""a"#).is_err());
+    }
+
+    #[test]
+    fn parse_area_effect_splits_name_at_first_colon() {
+        let area_effect: Box<dyn AreaEffect> = "OnceOnly: PushSelf: num = 2".parse().unwrap();
+        assert_eq!(
+            area_effect.effect_text(&Preferences::default()),
+            "初めて止まったときのみ: プレイヤーは2 マス進む。"
+        );
+    }
+
+    #[test]
+    fn preview_description_reveals_effects() {
+        let area = area_from_effect_list(&["PushSelf: num = 2"]);
+        let preferences = Preferences::default();
+        assert_eq!(
+            area.preview_description(&preferences),
+            "テスト\n\n効果\n- プレイヤーは2 マス進む。\n"
+        );
+        assert_eq!(
+            area.map_line(&preferences, 12),
+            "テスト / プレイヤーは2 マス進む。"
+        );
+    }
+
+    #[test]
+    fn preview_description_hides_effects() {
+        let area = area_from_effect_list(&["PushSelf: num = 2"]);
+        let mut preferences = Preferences::default();
+        preferences.set_reveal_effects(false);
+        assert_eq!(area.preview_description(&preferences), "テスト");
+        assert_eq!(area.map_line(&preferences, 12), "テスト");
+        assert_eq!(
+            area.area_description(&preferences),
+            "テスト\n\n効果\n- プレイヤーは2 マス進む。\n"
+        );
+    }
+
+    #[test]
+    fn veto_cancels_push_others_all_only_for_holder() {
+        let area = area_from_effect_list(&["PushOthersAll: num = 2"]);
+        let (player_order, mut player_status_table) =
+            player_status_table_from_position_list(&[("Alice", 3), ("Bob", 1), ("Carol", 2)]);
+        player_status_table.get_mut("Bob").unwrap().set_veto(true);
+        let before = player_status_table.clone();
+        area.execute(
+            "Alice",
+            &player_order,
+            &mut player_status_table,
+            &mut StdRng::seed_from_u64(0),
+            &mut HashMap::new(),
+            3,
+            10,
+        )
+        .unwrap();
+        assert_eq!(
+            diff_player_status_table(&before, &player_status_table),
+            vec![
+                "Alice.visited_area_set: {} -> {3}",
+                "Bob.has_veto: true -> false",
+                "Carol.position: 2 -> 4",
+            ]
+        );
+    }
+
+    #[test]
+    fn veto_cancels_confusion() {
+        let area = area_from_effect_list(&["Confuse: turns = 2"]);
+        let (player_order, mut player_status_table) =
+            player_status_table_from_position_list(&[("Alice", 3)]);
+        player_status_table.get_mut("Alice").unwrap().set_veto(true);
+        let before = player_status_table.clone();
+        area.execute(
+            "Alice",
+            &player_order,
+            &mut player_status_table,
+            &mut StdRng::seed_from_u64(0),
+            &mut HashMap::new(),
+            3,
+            10,
+        )
+        .unwrap();
+        assert_eq!(
+            diff_player_status_table(&before, &player_status_table),
+            vec![
+                "Alice.visited_area_set: {} -> {3}",
+                "Alice.has_veto: true -> false",
+            ]
+        );
+    }
+
+    #[test]
+    fn every_effect_has_japanese_help_text() {
+        let preferences = Preferences::default();
+        for (effect_name, input_format, area_effect) in area_effect_catalog() {
+            let help_text = area_effect.help_text(&preferences);
+            assert!(!help_text.is_ascii(), "{}: {}", effect_name, help_text);
+            assert!(
+                input_format.starts_with(&format!("`{}:", effect_name)),
+                "{}",
+                input_format
+            );
+        }
+    }
+
+    #[test]
+    fn once_only_skips_second_visit_but_not_other_player() {
+        let area = area_from_effect_list(&["OnceOnly: PushSelf: num = 2"]);
+        let (player_order, mut player_status_table) =
+            player_status_table_from_position_list(&[("Alice", 3), ("Bob", 3)]);
+        assert_eq!(
+            diff_after_execute(
+                &area,
+                "Alice",
+                &player_order,
+                &mut player_status_table,
+                3,
+                0
+            ),
+            vec!["Alice.position: 3 -> 5"]
+        );
+        player_status_table
+            .get_mut("Alice")
+            .unwrap()
+            .set_position(3);
+        assert!(diff_after_execute(
+            &area,
+            "Alice",
+            &player_order,
+            &mut player_status_table,
+            3,
+            0
+        )
+        .is_empty());
+        assert_eq!(
+            diff_after_execute(&area, "Bob", &player_order, &mut player_status_table, 3, 0),
+            vec!["Bob.position: 3 -> 5"]
+        );
+    }
+
+    #[test]
+    fn scaled_push_scales_with_number_of_players() {
+        let area = area_from_effect_list(&["ScaledPush: per_player = 1"]);
+        let (player_order, mut player_status_table) =
+            player_status_table_from_position_list(&[("Alice", 2), ("Bob", 0)]);
+        assert_eq!(
+            diff_after_execute(
+                &area,
+                "Alice",
+                &player_order,
+                &mut player_status_table,
+                2,
+                0
+            ),
+            vec!["Alice.position: 2 -> 4"]
+        );
+        let (player_order, mut player_status_table) = player_status_table_from_position_list(&[
+            ("Alice", 2),
+            ("Bob", 0),
+            ("Carol", 0),
+            ("Dave", 0),
+        ]);
+        assert_eq!(
+            diff_after_execute(
+                &area,
+                "Alice",
+                &player_order,
+                &mut player_status_table,
+                2,
+                0
+            ),
+            vec!["Alice.position: 2 -> 6"]
+        );
+    }
+
+    #[test]
+    fn handicap_skips_only_opponents_ahead() {
+        let area = area_from_effect_list(&["Handicap:"]);
+        let (player_order, mut player_status_table) =
+            player_status_table_from_position_list(&[("Alice", 3), ("Bob", 5), ("Carol", 1)]);
+        assert_eq!(
+            diff_after_execute(
+                &area,
+                "Alice",
+                &player_order,
+                &mut player_status_table,
+                3,
+                0
+            ),
+            vec!["Bob.num_skip: 0 -> 1"]
+        );
+    }
+
+    #[test]
+    fn mirror_reflects_across_center() {
+        let area = area_from_effect_list(&["Mirror:"]);
+        for (position, mirrored_position) in [(2, 8), (8, 2), (5, 5)] {
+            let (player_order, mut player_status_table) =
+                player_status_table_from_position_list(&[("Alice", position)]);
+            diff_after_execute(
+                &area,
+                "Alice",
+                &player_order,
+                &mut player_status_table,
+                position,
+                0,
+            );
+            assert_eq!(
+                player_status_table["Alice"].position(),
+                mirrored_position,
+                "{}",
+                position
+            );
+        }
+    }
+
+    #[test]
+    fn time_stop_adds_extra_turns() {
+        let area = area_from_effect_list(&["TimeStop: turns = 2"]);
+        let (player_order, mut player_status_table) =
+            player_status_table_from_position_list(&[("Alice", 3), ("Bob", 0)]);
+        assert_eq!(
+            diff_after_execute(
+                &area,
+                "Alice",
+                &player_order,
+                &mut player_status_table,
+                3,
+                0
+            ),
+            vec!["Alice.num_extra_turn: 0 -> 2"]
+        );
+    }
+
+    #[test]
+    fn leash_to_pack_pulls_back_only_too_far_leader() {
+        let area = area_from_effect_list(&["LeashToPack: max_lead = 2"]);
+        let (player_order, mut player_status_table) =
+            player_status_table_from_position_list(&[("Alice", 8), ("Bob", 3), ("Carol", 1)]);
+        assert_eq!(
+            diff_after_execute(
+                &area,
+                "Alice",
+                &player_order,
+                &mut player_status_table,
+                8,
+                0
+            ),
+            vec!["Alice.position: 8 -> 5"]
+        );
+        let (player_order, mut player_status_table) =
+            player_status_table_from_position_list(&[("Alice", 5), ("Bob", 3), ("Carol", 1)]);
+        assert!(diff_after_execute(
+            &area,
+            "Alice",
+            &player_order,
+            &mut player_status_table,
+            5,
+            0
+        )
+        .is_empty());
+    }
+
+    #[test]
+    fn description_text_prefers_localized_text() {
+        let preferences = Preferences::default();
+        let mut area = area_from_effect_list(&["PushSelf: num = 2"]);
+        assert_eq!(area.description_text(&preferences), "テスト");
+        area.set_localized_description(Language::Japanese, "日本語の説明".to_string());
+        assert_eq!(area.description_text(&preferences), "日本語の説明");
+        assert!(area
+            .area_description(&preferences)
+            .starts_with("日本語の説明\n"));
+        assert_eq!(area.description(), "テスト");
+    }
+
+    #[test]
+    fn haunt_applies_effects_to_previous_lander() {
+        let area = area_from_effect_list(&["Haunt:", "PushSelf: num = 1"]);
+        let (player_order, mut player_status_table) =
+            player_status_table_from_position_list(&[("Alice", 3), ("Bob", 3)]);
+        assert_eq!(
+            diff_after_execute(
+                &area,
+                "Alice",
+                &player_order,
+                &mut player_status_table,
+                3,
+                0
+            ),
+            vec![
+                "Alice.position: 3 -> 4",
+                "Alice.haunting_area_set: {} -> {3}"
+            ]
+        );
+        assert_eq!(
+            diff_after_execute(&area, "Bob", &player_order, &mut player_status_table, 3, 0),
+            vec![
+                "Alice.position: 4 -> 5",
+                "Alice.haunting_area_set: {3} -> {}",
+                "Bob.position: 3 -> 4",
+                "Bob.haunting_area_set: {} -> {3}"
+            ]
+        );
+    }
+}
@@ -5,14 +5,28 @@
 
 use crate::error::GameSystemError;
 use anyhow::Result;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 
 /// プレイヤーの状態
-#[derive(Debug, Default)]
+#[derive(Clone, Debug, Default, PartialEq)]
 pub struct PlayerStatus {
     position: usize,
     num_skip: u8,
     order_of_arrival: Option<u8>,
+    intro: Option<String>,
+    dice_boost: usize,
+    num_dice_boost: u8,
+    point: u32,
+    visited_area_set: HashSet<usize>,
+    is_waiting_for_pass: bool,
+    last_dice: Option<usize>,
+    banked_dice: Option<usize>,
+    num_extra_turn: u8,
+    num_confusion: u8,
+    has_veto: bool,
+    is_desperate: bool,
+    is_in_last_place: bool,
+    haunting_area_set: HashSet<usize>,
 }
 
 // impl Default for PlayerStatus {
@@ -26,6 +40,13 @@ pub struct PlayerStatus {
 // }
 
 impl PlayerStatus {
+    /// 自己紹介以外をゲーム開始時の状態に戻す
+    pub fn reset(&mut self) {
+        *self = Self {
+            intro: self.intro.take(),
+            ..Default::default()
+        };
+    }
     pub fn position(&self) -> usize {
         self.position
     }
@@ -41,12 +62,151 @@ impl PlayerStatus {
     pub fn sub_num_skip(&mut self, x: u8) {
         self.num_skip = self.num_skip.saturating_sub(x);
     }
+    /// 他のプレイヤーを飛ばして続けて行える手番の回数
+    pub fn num_extra_turn(&self) -> u8 {
+        self.num_extra_turn
+    }
+    pub fn add_num_extra_turn(&mut self, x: u8) {
+        self.num_extra_turn = self.num_extra_turn.saturating_add(x);
+    }
+    pub fn sub_num_extra_turn(&mut self, x: u8) {
+        self.num_extra_turn = self.num_extra_turn.saturating_sub(x);
+    }
+    /// サイコロの目だけ後ろへ進む状態が残っている手番の回数
+    pub fn num_confusion(&self) -> u8 {
+        self.num_confusion
+    }
+    pub fn add_num_confusion(&mut self, x: u8) {
+        self.num_confusion = self.num_confusion.saturating_add(x);
+    }
+    pub fn sub_num_confusion(&mut self, x: u8) {
+        self.num_confusion = self.num_confusion.saturating_sub(x);
+    }
+    /// 次にマスの効果で状態が変化したとき、それを取り消せるかどうか
+    pub fn has_veto(&self) -> bool {
+        self.has_veto
+    }
+    pub fn set_veto(&mut self, has_veto: bool) {
+        self.has_veto = has_veto;
+    }
+    /// マスの効果で変化しうる状態が`other`と異なるかどうか（拒否権や訪問済みのマスなどの記録は比べない）
+    pub fn is_changed_by_effect(&self, other: &Self) -> bool {
+        self.position != other.position
+            || self.num_skip != other.num_skip
+            || self.dice_boost != other.dice_boost
+            || self.num_dice_boost != other.num_dice_boost
+            || self.is_waiting_for_pass != other.is_waiting_for_pass
+            || self.banked_dice != other.banked_dice
+            || self.num_extra_turn != other.num_extra_turn
+            || self.num_confusion != other.num_confusion
+            || self.is_desperate != other.is_desperate
+    }
+    /// 最後尾にいる間だけサイコロの最大値が増える状態かどうか
+    pub fn set_desperate(&mut self, is_desperate: bool) {
+        self.is_desperate = is_desperate;
+    }
+    /// ゴールしていないプレイヤーの中で最も後ろにいるかどうか（サイコロを振るたびに更新される）
+    pub fn set_in_last_place(&mut self, is_in_last_place: bool) {
+        self.is_in_last_place = is_in_last_place;
+    }
+    /// 最後尾にいることによるサイコロの最大値の増加量
+    pub fn desperation_bonus(&self) -> usize {
+        if self.is_desperate && self.is_in_last_place {
+            1
+        } else {
+            0
+        }
+    }
+    /// `area_index`番目のマスに最後に止まったプレイヤーとして記録されているかどうか
+    pub fn is_haunting(&self, area_index: usize) -> bool {
+        self.haunting_area_set.contains(&area_index)
+    }
+    pub fn set_haunting(&mut self, area_index: usize, is_haunting: bool) {
+        if is_haunting {
+            self.haunting_area_set.insert(area_index);
+        } else {
+            self.haunting_area_set.remove(&area_index);
+        }
+    }
     pub fn order_of_arrival(&self) -> Option<u8> {
         self.order_of_arrival
     }
     pub fn set_order_of_arrival(&mut self, order_of_arrival: u8) {
         self.order_of_arrival = Some(order_of_arrival);
     }
+    /// ゴールした順位に応じて得られる得点
+    pub fn point(&self) -> u32 {
+        self.point
+    }
+    pub fn add_point(&mut self, x: u32) {
+        self.point = self.point.saturating_add(x);
+    }
+    /// サイコロの最大値の増加分（残り回数が0の場合は0）
+    pub fn dice_boost(&self) -> usize {
+        if self.num_dice_boost == 0 {
+            0
+        } else {
+            self.dice_boost
+        }
+    }
+    pub fn num_dice_boost(&self) -> u8 {
+        self.num_dice_boost
+    }
+    /// 次回以降`num_dice_boost`回の間、サイコロの最大値を`dice_boost`増やす（既存の増加は上書きされる）
+    pub fn set_dice_boost(&mut self, dice_boost: usize, num_dice_boost: u8) {
+        self.dice_boost = dice_boost;
+        self.num_dice_boost = num_dice_boost;
+    }
+    pub fn sub_num_dice_boost(&mut self, x: u8) {
+        self.num_dice_boost = self.num_dice_boost.saturating_sub(x);
+    }
+    /// タイトル画面に表示される自己紹介
+    pub fn intro(&self) -> Option<&str> {
+        self.intro.as_deref()
+    }
+    pub fn set_intro(&mut self, intro: Option<String>) {
+        self.intro = intro;
+    }
+    /// 他のプレイヤーに追い越されるまで動けない状態かどうか
+    pub fn is_waiting_for_pass(&self) -> bool {
+        self.is_waiting_for_pass
+    }
+    pub fn set_waiting_for_pass(&mut self, is_waiting_for_pass: bool) {
+        self.is_waiting_for_pass = is_waiting_for_pass;
+    }
+    /// 直前に振ったサイコロの値
+    pub fn last_dice(&self) -> Option<usize> {
+        self.last_dice
+    }
+    pub fn set_last_dice(&mut self, dice: usize) {
+        self.last_dice = Some(dice);
+    }
+    /// サイコロを振った手番を数え、ターン数で効果が切れる状態を一回分減らす
+    ///
+    /// 動けなかった手番も数える。混乱している手番だった場合は`true`を返す。
+    pub fn consume_dice_turn(&mut self, dice: usize) -> bool {
+        self.set_last_dice(dice);
+        self.sub_num_dice_boost(1);
+        let is_confused = self.num_confusion > 0;
+        self.sub_num_confusion(1);
+        is_confused
+    }
+    /// 後で使うためにサイコロの値を預ける（すでに預けている場合は何もしない）
+    pub fn bank_dice(&mut self, dice: usize) {
+        if self.banked_dice.is_none() {
+            self.banked_dice = Some(dice);
+        }
+    }
+    pub fn take_banked_dice(&mut self) -> Option<usize> {
+        self.banked_dice.take()
+    }
+    /// 効果が発動したマスとして`area_index`番目のマスを記録する
+    pub fn visit(&mut self, area_index: usize) {
+        self.visited_area_set.insert(area_index);
+    }
+    pub fn has_visited(&self, area_index: usize) -> bool {
+        self.visited_area_set.contains(&area_index)
+    }
     pub fn go_forward(&mut self, n: usize) {
         self.position = self.position.saturating_add(n);
     }
@@ -69,6 +229,16 @@ impl PlayerOrder for [String] {
         current_player: &str,
         player_status_table: &mut HashMap<String, PlayerStatus>,
     ) -> Result<Option<String>> {
+        // 追加の手番が残っている場合は他のプレイヤーの休みを消費せずに続けて手番を行う
+        let current_player_status = player_status_table
+            .get_mut(current_player)
+            .ok_or_else(|| GameSystemError::NotFoundPlayer(current_player.to_owned()))?;
+        if current_player_status.order_of_arrival().is_none()
+            && current_player_status.num_extra_turn() > 0
+        {
+            current_player_status.sub_num_extra_turn(1);
+            return Ok(Some(current_player.to_owned()));
+        }
         let mut player_cycle = self.iter().cycle();
         loop {
             if player_cycle.next().unwrap() == current_player {
@@ -88,3 +258,123 @@ impl PlayerOrder for [String] {
         Ok(None)
     }
 }
+
+/// テスト用に、プレイヤー名と位置の一覧から`player_order`と`player_status_table`を作る
+#[cfg(test)]
+pub fn player_status_table_from_position_list(
+    position_list: &[(&str, usize)],
+) -> (Vec<String>, HashMap<String, PlayerStatus>) {
+    let player_order = position_list
+        .iter()
+        .map(|(player, _)| player.to_string())
+        .collect();
+    let player_status_table = position_list
+        .iter()
+        .map(|&(player, position)| {
+            let mut player_status = PlayerStatus::default();
+            player_status.set_position(position);
+            (player.to_string(), player_status)
+        })
+        .collect();
+    (player_order, player_status_table)
+}
+
+/// テスト用に、二つの`player_status_table`を比べて変化したプレイヤーと項目を`プレイヤー.項目: 前 -> 後`の形式で列挙する
+#[cfg(test)]
+pub fn diff_player_status_table(
+    before: &HashMap<String, PlayerStatus>,
+    after: &HashMap<String, PlayerStatus>,
+) -> Vec<String> {
+    // `..`を使わずに分解するので、項目を追加したときにここへ書き足し忘れるとコンパイルエラーになる
+    macro_rules! push_field_diff {
+        ($diff_list:expr, $player:expr, $before:expr, $after:expr, $($field:ident),+) => {{
+            let PlayerStatus { $($field),+ } = $before;
+            $(if *$field != $after.$field {
+                $diff_list.push(format!(
+                    "{}.{}: {:?} -> {:?}",
+                    $player,
+                    stringify!($field),
+                    $field,
+                    $after.$field
+                ));
+            })+
+        }};
+    }
+    let mut player_list: Vec<&String> = before.keys().chain(after.keys()).collect();
+    player_list.sort();
+    player_list.dedup();
+    let mut diff_list = Vec::new();
+    for player in player_list {
+        match (before.get(player), after.get(player)) {
+            (Some(before), Some(after)) => push_field_diff!(
+                diff_list,
+                player,
+                before,
+                after,
+                position,
+                num_skip,
+                order_of_arrival,
+                intro,
+                dice_boost,
+                num_dice_boost,
+                point,
+                visited_area_set,
+                is_waiting_for_pass,
+                last_dice,
+                banked_dice,
+                num_extra_turn,
+                num_confusion,
+                has_veto,
+                is_desperate,
+                is_in_last_place,
+                haunting_area_set
+            ),
+            (Some(_), None) => diff_list.push(format!("{}: removed", player)),
+            (None, Some(_)) => diff_list.push(format!("{}: added", player)),
+            (None, None) => {}
+        }
+    }
+    diff_list
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn diff_reports_changed_position() {
+        let (_, before) = player_status_table_from_position_list(&[("Alice", 0), ("Bob", 2)]);
+        let mut after = before.clone();
+        after.get_mut("Alice").unwrap().go_forward(3);
+        assert_eq!(
+            diff_player_status_table(&before, &after),
+            vec!["Alice.position: 0 -> 3"]
+        );
+    }
+
+    #[test]
+    fn diff_of_same_table_is_empty() {
+        let (_, before) = player_status_table_from_position_list(&[("Alice", 1)]);
+        assert!(diff_player_status_table(&before, &before.clone()).is_empty());
+    }
+
+    #[test]
+    fn extra_turns_give_consecutive_turns() {
+        let (player_order, mut player_status_table) =
+            player_status_table_from_position_list(&[("Alice", 0), ("Bob", 0)]);
+        player_status_table
+            .get_mut("Alice")
+            .unwrap()
+            .add_num_extra_turn(2);
+        let mut current_player = "Alice".to_string();
+        let mut turn_list = Vec::new();
+        for _ in 0..4 {
+            current_player = player_order
+                .next_player(&current_player, &mut player_status_table)
+                .unwrap()
+                .unwrap();
+            turn_list.push(current_player.clone());
+        }
+        assert_eq!(turn_list, vec!["Alice", "Alice", "Bob", "Alice"]);
+    }
+}
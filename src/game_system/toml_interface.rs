@@ -6,9 +6,12 @@
 use crate::error::GameSystemError;
 use crate::game_system::area::{Area, AreaEffect, NoEffect};
 use crate::game_system::player_status::PlayerStatus;
-use crate::game_system::world::World;
+use crate::game_system::world::{Collision, Rule, World};
+use crate::preferences::Language;
 use anyhow::{Context, Result};
-use serde_derive::Deserialize;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use serde_derive::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::fs;
 use std::path::Path;
@@ -18,35 +21,63 @@ use toml;
 #[derive(Debug, Deserialize)]
 struct PlayerListDescription {
     player: Vec<StatusDescription>,
+    #[serde(default)]
+    meta: PlayerListMeta,
+}
+
+/// プレイヤーファイルの覚え書き（ゲームには影響しない）
+#[derive(Debug, Default, Deserialize)]
+pub struct PlayerListMeta {
+    description: Option<String>,
+    created: Option<String>,
+}
+impl PlayerListMeta {
+    pub fn description(&self) -> Option<&str> {
+        self.description.as_deref()
+    }
+    pub fn created(&self) -> Option<&str> {
+        self.created.as_deref()
+    }
 }
 
 #[derive(Debug, Deserialize)]
 struct StatusDescription {
     name: String,
+    intro: Option<String>,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, Serialize)]
 struct WorldDescription {
     general: WorldSettingDescription,
     area: Vec<AreaDescription>,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, Serialize)]
 struct WorldSettingDescription {
     title: String,
     opening_msg: String,
     start_description: String,
     goal_description: String,
     dice_max: usize,
+    arrival_points: Option<Vec<u32>>,
+    collision: Option<Collision>,
+    goal_bonus: Option<u32>,
+    reverse_goal: Option<bool>,
+    clamp_dice_max: Option<bool>,
+    min_players: Option<usize>,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, Serialize)]
 struct AreaDescription {
     description: String,
+    description_ja: Option<String>,
+    goal_bonus: Option<u32>,
+    /// 効果から移動先として指定するためのマスの名前
+    label: Option<String>,
     effect: Option<Vec<AreaEffectDescription>>,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, Serialize)]
 struct AreaEffectDescription {
     element: String,
 }
@@ -64,24 +95,43 @@ pub fn read_player_list_from_file(
         if player_status_table.contains_key(&player.name) {
             return Err(GameSystemError::DuplicatePlayer(player.name).into());
         } else {
-            player_status_table.insert(player.name.to_owned(), PlayerStatus::default());
+            let mut player_status = PlayerStatus::default();
+            player_status.set_intro(player.intro);
+            player_status_table.insert(player.name.to_owned(), player_status);
             player_order.push(player.name);
         }
     }
     Ok((player_order, player_status_table))
 }
 
+pub fn read_player_list_meta_from_file(file_path: &Path) -> Result<PlayerListMeta> {
+    let file_contents = fs::read_to_string(file_path)
+        .with_context(|| format!("failed to read {}", file_path.display()))?;
+    let player_description: PlayerListDescription = toml::from_str(&file_contents)
+        .with_context(|| format!("failed to parse {}", file_path.display()))?;
+    Ok(player_description.meta)
+}
+
 pub fn read_world_from_file(file_path: &Path) -> Result<World> {
     let file_contents = fs::read_to_string(file_path)
         .with_context(|| format!("failed to read {}", file_path.display()))?;
     let world_description: WorldDescription = toml::from_str(&file_contents)
         .with_context(|| format!("failed to parse {}", file_path.display()))?;
+    build_world(world_description)
+}
+
+/// 盤面ファイルの記述から盤面を作成する
+fn build_world(world_description: WorldDescription) -> Result<World> {
     let mut area_list = vec![Area::new(
         world_description.general.start_description,
         vec![Box::new(NoEffect::new())],
     )];
     for (i_area, area_description) in world_description.area.into_iter().enumerate() {
         let mut area_effect_list = Vec::<Box<dyn AreaEffect>>::new();
+        let mut area_effect_source_list = Vec::new();
+        if area_description.goal_bonus.is_some() && area_description.effect.is_some() {
+            return Err(GameSystemError::EffectOnGoalTier(i_area + 1).into());
+        }
         if let Some(area_effect_description_list) = area_description.effect {
             for (i_effect, area_effect_description) in
                 area_effect_description_list.into_iter().enumerate()
@@ -94,21 +144,238 @@ pub fn read_world_from_file(file_path: &Path) -> Result<World> {
                             i_area + 1
                         )
                     })?,
-                )
+                );
+                area_effect_source_list.push(area_effect_description.element);
             }
         } else {
             area_effect_list.push(Box::new(NoEffect::new()));
         };
-        area_list.push(Area::new(area_description.description, area_effect_list));
+        let mut area = Area::new(area_description.description, area_effect_list);
+        area.set_goal_bonus(area_description.goal_bonus);
+        area.set_label(area_description.label);
+        area.set_effect_source_list(area_effect_source_list);
+        if let Some(description_ja) = area_description.description_ja {
+            area.set_localized_description(Language::Japanese, description_ja);
+        }
+        area_list.push(area);
     }
     area_list.push(Area::new(
         world_description.general.goal_description,
         vec![Box::new(NoEffect::new())],
     ));
+    // ゴールから出発してスタートを目指す場合は盤面を逆順にする
+    if world_description.general.reverse_goal.unwrap_or(false) {
+        area_list.reverse();
+    }
+    area_list
+        .last_mut()
+        .unwrap()
+        .set_goal_bonus(world_description.general.goal_bonus);
+    // 盤面を逆順にした後の番号でラベルを解決する
+    let mut label_table = HashMap::new();
+    for (area_index, area) in area_list.iter().enumerate() {
+        if let Some(label) = area.label() {
+            if label_table.insert(label.to_owned(), area_index).is_some() {
+                return Err(GameSystemError::DuplicateLabel(label.to_owned()).into());
+            }
+        }
+    }
+    for area in area_list.iter_mut() {
+        area.resolve_label(&label_table)?;
+    }
     Ok(World::new(
         world_description.general.title,
         world_description.general.opening_msg,
         world_description.general.dice_max,
         area_list,
+        Rule {
+            arrival_point_list: world_description.general.arrival_points.unwrap_or_default(),
+            collision: world_description.general.collision.unwrap_or_default(),
+            clamp_dice_max: world_description.general.clamp_dice_max.unwrap_or(false),
+            min_num_player: world_description.general.min_players.unwrap_or(1),
+        },
     ))
 }
+
+/// ランダムな盤面に置く効果の候補
+fn random_area_effect(rng: &mut StdRng) -> String {
+    match rng.gen_range(0..7) {
+        0 => format!("PushSelf: num = {}", rng.gen_range(1..=3)),
+        1 => format!("PullSelf: num = {}", rng.gen_range(1..=3)),
+        2 => "SkipSelf: times = 1".to_string(),
+        3 => format!("PushOthersAll: num = {}", rng.gen_range(1..=2)),
+        4 => format!("PullOthersAll: num = {}", rng.gen_range(1..=2)),
+        5 => format!(
+            "BoostDice: delta = {}, turns = {}",
+            rng.gen_range(1..=2),
+            rng.gen_range(1..=3)
+        ),
+        _ => "GoToStart:".to_string(),
+    }
+}
+
+/// `seed`から`num_area`マスのランダムな盤面ファイルの内容を作成する
+///
+/// ゴールに到達できない盤面になった場合は作り直し、一定回数失敗した場合は効果のない盤面にする。
+/// `num_area`が0の場合や、効果のない盤面でもゴールに到達できない（`dice_max`が0の）場合はエラーを返す。
+pub fn generate_world_toml(num_area: usize, dice_max: usize, seed: u64) -> Result<String> {
+    const MAX_NUM_ATTEMPT: usize = 100;
+    // マスが一つもないとTOMLとして書き出せない
+    if num_area == 0 {
+        return Err(GameSystemError::NoArea.into());
+    }
+    let mut rng = StdRng::seed_from_u64(seed);
+    for i_attempt in 0..=MAX_NUM_ATTEMPT {
+        let area = (1..=num_area)
+            .map(|i_area| AreaDescription {
+                description: format!("{}マス目", i_area),
+                description_ja: None,
+                goal_bonus: None,
+                label: None,
+                effect: if i_attempt < MAX_NUM_ATTEMPT && rng.gen_bool(0.5) {
+                    Some(vec![AreaEffectDescription {
+                        element: random_area_effect(&mut rng),
+                    }])
+                } else {
+                    None
+                },
+            })
+            .collect();
+        let world_description = WorldDescription {
+            general: WorldSettingDescription {
+                title: format!("ランダムな盤面（シード: {}）", seed),
+                opening_msg: "ランダムに作成された盤面です。".to_string(),
+                start_description: "スタート".to_string(),
+                goal_description: "ゴール".to_string(),
+                dice_max,
+                arrival_points: None,
+                collision: None,
+                goal_bonus: None,
+                reverse_goal: None,
+                clamp_dice_max: None,
+                min_players: None,
+            },
+            area,
+        };
+        let world_toml = toml::to_string(&world_description)?;
+        // 書き出した内容を読み込み直して検査する
+        let world = build_world(toml::from_str(&world_toml)?)?;
+        if world.can_reach_goal()? {
+            return Ok(world_toml);
+        }
+    }
+    Err(GameSystemError::UnreachableGoal.into())
+}
+
+/// テスト用に盤面ファイルの内容から盤面を作成する
+#[cfg(test)]
+pub fn world_from_str(world_toml: &str) -> World {
+    build_world(toml::from_str(world_toml).unwrap()).unwrap()
+}
+
+/// テスト用にマスごとの効果の一覧から盤面を作成する（効果の一覧が空のマスは効果なし）
+#[cfg(test)]
+pub fn world_from_effect_list(dice_max: usize, effect_list: &[&[&str]]) -> World {
+    build_world(WorldDescription {
+        general: WorldSettingDescription {
+            title: "テスト".to_string(),
+            opening_msg: String::new(),
+            start_description: "スタート".to_string(),
+            goal_description: "ゴール".to_string(),
+            dice_max,
+            arrival_points: None,
+            collision: None,
+            goal_bonus: None,
+            reverse_goal: None,
+            clamp_dice_max: None,
+            min_players: None,
+        },
+        area: effect_list
+            .iter()
+            .enumerate()
+            .map(|(i_area, area_effect_list)| AreaDescription {
+                description: format!("{}マス目", i_area + 1),
+                description_ja: None,
+                goal_bonus: None,
+                label: None,
+                effect: if area_effect_list.is_empty() {
+                    None
+                } else {
+                    Some(
+                        area_effect_list
+                            .iter()
+                            .map(|element| AreaEffectDescription {
+                                element: element.to_string(),
+                            })
+                            .collect(),
+                    )
+                },
+            })
+            .collect(),
+    })
+    .unwrap()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn generated_world_passes_validation_and_has_num_area_areas() {
+        for seed in 0..10 {
+            let world_toml = generate_world_toml(20, 4, seed).unwrap();
+            let world = build_world(toml::from_str(&world_toml).unwrap()).unwrap();
+            // スタートとゴールの分を含む
+            assert_eq!(world.area_list().len(), 22);
+            assert!(world.can_reach_goal().unwrap());
+        }
+    }
+
+    #[test]
+    fn generate_world_toml_rejects_zero_areas() {
+        let error = generate_world_toml(0, 4, 0).unwrap_err();
+        assert!(matches!(
+            error.downcast_ref::<GameSystemError>(),
+            Some(GameSystemError::NoArea)
+        ));
+    }
+
+    #[test]
+    fn build_world_rejects_unknown_and_duplicate_labels() {
+        let world_description = |label_list: [&str; 2]| -> WorldDescription {
+            toml::from_str(&format!(
+                r#"
+                [general]
+                title = "テスト"
+                opening_msg = ""
+                start_description = "スタート"
+                goal_description = "ゴール"
+                dice_max = 3
+
+                [[area]]
+                description = "1マス目"
+                label = "{}"
+                [[area.effect]]
+                element = 'GotoLabelIfFlag: flag = "gate_open", label = "shortcut"'
+
+                [[area]]
+                description = "2マス目"
+                label = "{}"
+                "#,
+                label_list[0], label_list[1]
+            ))
+            .unwrap()
+        };
+        assert!(build_world(world_description(["start", "shortcut"])).is_ok());
+        let error = build_world(world_description(["start", "goal"])).unwrap_err();
+        assert!(matches!(
+            error.downcast_ref::<GameSystemError>(),
+            Some(GameSystemError::NotFoundLabel(label)) if label == "shortcut"
+        ));
+        let error = build_world(world_description(["shortcut", "shortcut"])).unwrap_err();
+        assert!(matches!(
+            error.downcast_ref::<GameSystemError>(),
+            Some(GameSystemError::DuplicateLabel(label)) if label == "shortcut"
+        ));
+    }
+}
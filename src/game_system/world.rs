@@ -6,9 +6,39 @@
 use crate::error::GameSystemError;
 use crate::game_system::area::Area;
 use crate::game_system::player_status::PlayerStatus;
-use crate::preferences::Preferences;
-use rand::rngs::ThreadRng;
-use std::collections::HashMap;
+use crate::preferences::{Language, Preferences};
+use rand::rngs::{StdRng, ThreadRng};
+use rand::SeedableRng;
+use serde_derive::{Deserialize, Serialize};
+use std::collections::{HashMap, VecDeque};
+
+/// 地図に表示するマスの説明の最大文字数
+const MAP_DESCRIPTION_MAX_LEN: usize = 12;
+
+/// 移動した先のマスに他のプレイヤーがいた場合の扱い
+#[derive(Clone, Copy, Debug, Default, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Collision {
+    /// 同じマスに止まる
+    #[default]
+    Stack,
+    /// 空いているマスまで1マスずつ進む
+    BumpForward,
+    /// 先にいたプレイヤーと移動前のマスを入れ替える
+    Swap,
+}
+
+/// 盤面ごとに設定できる規則
+#[derive(Clone, Debug, Default)]
+pub struct Rule {
+    /// 順位ごとの得点（1位から順に並び、範囲外の順位は0点）
+    pub arrival_point_list: Vec<u32>,
+    pub collision: Collision,
+    /// サイコロの最大値をスタートから一回でゴールできない値に制限するかどうか
+    pub clamp_dice_max: bool,
+    /// ゲームを始めるのに必要なプレイヤーの人数
+    pub min_num_player: usize,
+}
 
 #[derive(Debug)]
 pub struct World {
@@ -16,19 +46,30 @@ pub struct World {
     opening_msg: String,
     dice_max: usize,
     area_list: Vec<Area>,
+    rule: Rule,
     num_goal_player: u8,
     rng: ThreadRng,
+    /// マスの効果で立てられる、盤面全体で共有する名前付きのフラグ
+    flag_table: HashMap<String, bool>,
 }
 
 impl World {
-    pub fn new(title: String, opening_msg: String, dice_max: usize, area_list: Vec<Area>) -> Self {
+    pub fn new(
+        title: String,
+        opening_msg: String,
+        dice_max: usize,
+        area_list: Vec<Area>,
+        rule: Rule,
+    ) -> Self {
         Self {
             title,
             opening_msg,
             dice_max,
             area_list,
+            rule,
             num_goal_player: 0,
             rng: rand::thread_rng(),
+            flag_table: HashMap::new(),
         }
     }
     pub fn title(&self) -> &str {
@@ -37,17 +78,131 @@ impl World {
     pub fn opening_msg(&self) -> &str {
         &self.opening_msg
     }
-    pub fn dice_max(&self) -> usize {
-        self.dice_max
+    pub fn flag_table(&self) -> &HashMap<String, bool> {
+        &self.flag_table
+    }
+    /// プレイヤーごとの増加分を含めたサイコロの最大値
+    ///
+    /// `clamp_dice_max`が有効な場合は、スタートから一回でゴールできない値に制限する。
+    pub fn player_dice_max(&self, player_status: &PlayerStatus) -> usize {
+        let dice_max = self
+            .dice_max
+            .saturating_add(player_status.dice_boost())
+            .saturating_add(player_status.desperation_bonus());
+        if self.rule.clamp_dice_max {
+            dice_max.min(self.clamped_dice_max())
+        } else {
+            dice_max
+        }
+    }
+    /// `clamp_dice_max`が有効な場合のサイコロの最大値の上限（ゴールの一つ手前までのマス数、最低でも1）
+    fn clamped_dice_max(&self) -> usize {
+        (self.area_list.len() - 1).saturating_sub(1).max(1)
+    }
+    /// 決まったマスへ移動させる効果による（移動元, 移動先）の一覧
+    pub fn jump_edges(&self) -> Vec<(usize, usize)> {
+        let goal_position = self.area_list.len() - 1;
+        let mut jump_edges = Vec::new();
+        for (area_index, area) in self.area_list.iter().enumerate() {
+            for target_index in area.jump_target_list(area_index, goal_position) {
+                jump_edges.push((area_index, target_index));
+            }
+        }
+        jump_edges
+    }
+    /// スタートから一人で進んだ場合にゴールへ到達できるかどうか
+    ///
+    /// マスの位置を頂点、サイコロの目ごとの移動先を辺とするグラフを幅優先探索する。
+    /// ランダムな効果はゲームの乱数を進めないよう固定の種の乱数による結果の一例で判定する。
+    /// フラグは立っていないものとして判定する。
+    pub fn can_reach_goal(&self) -> Result<bool, GameSystemError> {
+        let goal_position = self.area_list.len() - 1;
+        let player = String::new();
+        let player_order = vec![player.clone()];
+        let dice_max = self.player_dice_max(&PlayerStatus::default());
+        let mut rng = StdRng::seed_from_u64(0);
+        let mut visited_list = vec![false; goal_position];
+        let mut position_queue = VecDeque::from([0]);
+        visited_list[0] = true;
+        while let Some(position) = position_queue.pop_front() {
+            for dice in 1..=dice_max {
+                let landing_position = (position + dice).min(goal_position);
+                let mut player_status = PlayerStatus::default();
+                player_status.set_position(landing_position);
+                let mut player_status_table = HashMap::from([(player.clone(), player_status)]);
+                self.area_list[landing_position].execute(
+                    &player,
+                    &player_order,
+                    &mut player_status_table,
+                    &mut rng,
+                    &mut HashMap::new(),
+                    landing_position,
+                    goal_position,
+                )?;
+                let next_position = player_status_table
+                    .get(&player)
+                    .ok_or_else(|| GameSystemError::NotFoundPlayer(player.to_owned()))?
+                    .position();
+                if next_position >= goal_position
+                    || self.area_list[landing_position].goal_bonus().is_some()
+                    || self.area_list[next_position].goal_bonus().is_some()
+                {
+                    return Ok(true);
+                }
+                if !visited_list[next_position] {
+                    visited_list[next_position] = true;
+                    position_queue.push_back(next_position);
+                }
+            }
+        }
+        Ok(false)
+    }
+    /// 盤面の作成時に見落としやすい問題の一覧
+    pub fn warning_list(&self, preferences: &Preferences) -> Vec<String> {
+        let mut warning_list = Vec::new();
+        let goal_position = self.area_list.len() - 1;
+        if self.dice_max >= goal_position {
+            warning_list.push(match preferences.language() {
+                Language::Japanese => {
+                    if self.rule.clamp_dice_max {
+                        format!(
+                            "サイコロの最大値（{}）がスタートからゴールまでのマス数（{}）以上のため、最初の一回でゴールできないよう最大値を{}に制限します。",
+                            self.dice_max,
+                            goal_position,
+                            self.clamped_dice_max()
+                        )
+                    } else {
+                        format!(
+                            "サイコロの最大値（{}）がスタートからゴールまでのマス数（{}）以上のため、最初の一回でゴールできます。",
+                            self.dice_max, goal_position
+                        )
+                    }
+                }
+            });
+        }
+        warning_list
+    }
+    pub fn min_num_player(&self) -> usize {
+        self.rule.min_num_player
     }
     pub fn area_list(&self) -> &Vec<Area> {
         &self.area_list
     }
-    pub fn start_description(&self, preferences: &Preferences) -> String {
-        self.area_list
-            .first()
-            .unwrap()
-            .area_description(preferences)
+    /// 順位やゴールのマスによる得点があるかどうか
+    pub fn has_point(&self) -> bool {
+        !self.rule.arrival_point_list.is_empty()
+            || self
+                .area_list
+                .iter()
+                .any(|area| area.goal_bonus().is_some())
+    }
+    /// ゴールしたプレイヤーの数と全プレイヤーの状態をゲーム開始時に戻す
+    pub fn reset(&mut self, player_status_table: &mut HashMap<String, PlayerStatus>) {
+        self.num_goal_player = 0;
+        self.flag_table.clear();
+        for player_status in player_status_table.values_mut() {
+            player_status.reset();
+        }
     }
     pub fn dice_roll(
         &mut self,
@@ -57,13 +212,50 @@ impl World {
         player_order: &[String],
         player_status_table: &mut HashMap<String, PlayerStatus>,
     ) -> Result<String, GameSystemError> {
-        if dice < 1 || self.dice_max < dice {
+        let current_player_status = player_status_table
+            .get_mut(current_player)
+            .ok_or_else(|| GameSystemError::NotFoundPlayer(current_player.to_owned()))?;
+        if dice < 1 || self.player_dice_max(current_player_status) < dice {
             return Err(GameSystemError::OutOfRangeDice(dice));
         }
-        player_status_table
+        if current_player_status.is_waiting_for_pass() {
+            if player_order.iter().any(|player| {
+                player != current_player
+                    && player_status_table
+                        .get(player)
+                        .is_some_and(|player_status| player_status.order_of_arrival().is_none())
+            }) {
+                let current_player_status = player_status_table
+                    .get_mut(current_player)
+                    .ok_or_else(|| GameSystemError::NotFoundPlayer(current_player.to_owned()))?;
+                current_player_status.consume_dice_turn(dice);
+                return Ok(match preferences.language() {
+                    Language::Japanese => {
+                        "他のプレイヤーに追い越されるまで動けません。".to_string()
+                    }
+                });
+            }
+            // 待つ相手がいない場合はそのまま解除する
+            player_status_table
+                .get_mut(current_player)
+                .ok_or_else(|| GameSystemError::NotFoundPlayer(current_player.to_owned()))?
+                .set_waiting_for_pass(false);
+        }
+        let position_before_dice_roll: HashMap<String, usize> = player_status_table
+            .iter()
+            .filter(|(_, player_status)| player_status.order_of_arrival().is_none())
+            .map(|(player, player_status)| (player.to_owned(), player_status.position()))
+            .collect();
+        let current_player_status = player_status_table
             .get_mut(current_player)
-            .ok_or_else(|| GameSystemError::NotFoundPlayer(current_player.to_owned()))?
-            .go_forward(dice);
+            .ok_or_else(|| GameSystemError::NotFoundPlayer(current_player.to_owned()))?;
+        let previous_position = current_player_status.position();
+        if current_player_status.consume_dice_turn(dice) {
+            // 混乱している間はサイコロの目だけ戻る（スタートより前には戻らない）
+            current_player_status.go_backward(dice);
+        } else {
+            current_player_status.go_forward(dice);
+        }
         self.check_goal_player(player_status_table);
         let current_player_position = player_status_table
             .get_mut(current_player)
@@ -82,30 +274,813 @@ impl World {
                 player_order,
                 player_status_table,
                 &mut self.rng,
+                &mut self.flag_table,
+                current_player_position,
+                self.area_list.len() - 1,
             )?;
         self.check_goal_player(player_status_table);
-        Ok(self
-            .area_list
-            .get(current_player_position)
-            .ok_or_else(|| {
-                GameSystemError::OutOfRangePosition(
-                    current_player.to_owned(),
+        self.resolve_collision(
+            current_player,
+            previous_position,
+            player_order,
+            player_status_table,
+        )?;
+        self.check_goal_player(player_status_table);
+        release_waiting_for_pass(
+            &position_before_dice_roll,
+            player_order,
+            player_status_table,
+        )?;
+        update_last_place(player_status_table);
+        let area = self.area_list.get(current_player_position).ok_or_else(|| {
+            GameSystemError::OutOfRangePosition(current_player.to_owned(), current_player_position)
+        })?;
+        let mut text = area.area_description(preferences);
+        if area.show_map() {
+            let position_after_effect = player_status_table
+                .get(current_player)
+                .ok_or_else(|| GameSystemError::NotFoundPlayer(current_player.to_owned()))?
+                .position();
+            text += "\n";
+            text += &self.map_text(preferences, position_after_effect);
+        }
+        Ok(text)
+    }
+    /// `position`より先のゴールまでのマスを一行ずつ並べた地図
+    fn map_text(&self, preferences: &Preferences, position: usize) -> String {
+        let mut text = String::new();
+        match preferences.language() {
+            Language::Japanese => text += "地図\n",
+        }
+        for (area_index, area) in self.area_list.iter().enumerate().skip(position + 1) {
+            text += &format!(
+                "{:>3}: {}\n",
+                area_index,
+                area.map_line(preferences, MAP_DESCRIPTION_MAX_LEN)
+            );
+        }
+        text
+    }
+    /// 効果の発動後、`collision`に従って同じマスにいるプレイヤーを処理する
+    ///
+    /// スタートとゴールのマス、およびゴールしたプレイヤーは対象外とする。
+    fn resolve_collision(
+        &self,
+        current_player: &str,
+        previous_position: usize,
+        player_order: &[String],
+        player_status_table: &mut HashMap<String, PlayerStatus>,
+    ) -> Result<(), GameSystemError> {
+        let goal_position = self.area_list.len() - 1;
+        let current_player_status = player_status_table
+            .get(current_player)
+            .ok_or_else(|| GameSystemError::NotFoundPlayer(current_player.to_owned()))?;
+        let current_player_position = current_player_status.position();
+        if current_player_status.order_of_arrival().is_some()
+            || current_player_position == 0
+            || current_player_position >= goal_position
+        {
+            return Ok(());
+        }
+        match self.rule.collision {
+            Collision::Stack => {}
+            Collision::BumpForward => {
+                // ゴールに着くまでに必ず止まるので無限に進み続けることはない
+                let mut position = current_player_position;
+                while position < goal_position
+                    && find_occupant(current_player, position, player_order, player_status_table)?
+                        .is_some()
+                {
+                    position += 1;
+                }
+                player_status_table
+                    .get_mut(current_player)
+                    .ok_or_else(|| GameSystemError::NotFoundPlayer(current_player.to_owned()))?
+                    .set_position(position);
+            }
+            Collision::Swap => {
+                if let Some(occupant) = find_occupant(
+                    current_player,
                     current_player_position,
-                )
-            })?
-            .area_description(preferences))
+                    player_order,
+                    player_status_table,
+                )? {
+                    player_status_table
+                        .get_mut(&occupant)
+                        .ok_or_else(|| GameSystemError::NotFoundPlayer(occupant.to_owned()))?
+                        .set_position(previous_position);
+                }
+            }
+        }
+        Ok(())
     }
+    /// ゴールのマス、またはボーナスのあるマスにいるプレイヤーをゴールさせる
     fn check_goal_player(&mut self, player_status_table: &mut HashMap<String, PlayerStatus>) {
+        let goal_position = self.area_list.len() - 1;
         let mut num_goal_player = 0;
         for player_status in player_status_table.values_mut() {
-            if player_status.order_of_arrival() == None
-                && player_status.position() >= self.area_list.len() - 1
-            {
-                player_status.set_order_of_arrival(self.num_goal_player + 1);
-                player_status.set_position(self.area_list.len() - 1);
-                num_goal_player += 1;
+            if player_status.order_of_arrival().is_some() {
+                continue;
+            }
+            if player_status.position() >= goal_position {
+                player_status.set_position(goal_position);
             }
+            let goal_bonus = match self.area_list[player_status.position()].goal_bonus() {
+                Some(goal_bonus) => goal_bonus,
+                None if player_status.position() == goal_position => 0,
+                None => continue,
+            };
+            player_status.set_order_of_arrival(self.num_goal_player + 1);
+            player_status.add_point(
+                self.rule
+                    .arrival_point_list
+                    .get(usize::from(self.num_goal_player))
+                    .copied()
+                    .unwrap_or(0)
+                    .saturating_add(goal_bonus),
+            );
+            num_goal_player += 1;
         }
         self.num_goal_player += num_goal_player;
     }
 }
+
+/// `position`にいる、ゴールしていない`current_player`以外のプレイヤーを`player_order`の順に探す
+fn find_occupant(
+    current_player: &str,
+    position: usize,
+    player_order: &[String],
+    player_status_table: &HashMap<String, PlayerStatus>,
+) -> Result<Option<String>, GameSystemError> {
+    for player in player_order {
+        let player_status = player_status_table
+            .get(player)
+            .ok_or_else(|| GameSystemError::NotFoundPlayer(player.to_owned()))?;
+        if player != current_player
+            && player_status.order_of_arrival().is_none()
+            && player_status.position() == position
+        {
+            return Ok(Some(player.to_owned()));
+        }
+    }
+    Ok(None)
+}
+
+/// 追い越されるまで動けないプレイヤーのうち、追い越されたプレイヤーを解除する
+///
+/// `position_before_dice_roll`はサイコロを振る前にゴールしていなかったプレイヤーの位置。
+/// 他のプレイヤーが全員前にいる場合は、誰かが前に進めば追い越されたとみなす。
+/// ゴールしていない他のプレイヤーがいなくなった場合も解除する。
+fn release_waiting_for_pass(
+    position_before_dice_roll: &HashMap<String, usize>,
+    player_order: &[String],
+    player_status_table: &mut HashMap<String, PlayerStatus>,
+) -> Result<(), GameSystemError> {
+    let mut released_player_list = Vec::new();
+    for waiting_player in player_order {
+        let waiting_player_status = player_status_table
+            .get(waiting_player)
+            .ok_or_else(|| GameSystemError::NotFoundPlayer(waiting_player.to_owned()))?;
+        if !waiting_player_status.is_waiting_for_pass()
+            || waiting_player_status.order_of_arrival().is_some()
+        {
+            continue;
+        }
+        let waiting_position = waiting_player_status.position();
+        let mut exists_opponent = false;
+        let mut is_last = true;
+        let mut exists_overtaking_opponent = false;
+        let mut exists_moving_ahead_opponent = false;
+        for opponent in player_order {
+            if opponent == waiting_player {
+                continue;
+            }
+            let opponent_status = player_status_table
+                .get(opponent)
+                .ok_or_else(|| GameSystemError::NotFoundPlayer(opponent.to_owned()))?;
+            if opponent_status.order_of_arrival().is_none() {
+                exists_opponent = true;
+            }
+            if let Some(&position_before) = position_before_dice_roll.get(opponent) {
+                let position_after = opponent_status.position();
+                if position_before <= waiting_position {
+                    is_last = false;
+                    if position_after > waiting_position {
+                        exists_overtaking_opponent = true;
+                    }
+                } else if position_after > position_before {
+                    exists_moving_ahead_opponent = true;
+                }
+            }
+        }
+        if !exists_opponent || exists_overtaking_opponent || is_last && exists_moving_ahead_opponent
+        {
+            released_player_list.push(waiting_player);
+        }
+    }
+    for released_player in released_player_list {
+        player_status_table
+            .get_mut(released_player)
+            .ok_or_else(|| GameSystemError::NotFoundPlayer(released_player.to_owned()))?
+            .set_waiting_for_pass(false);
+    }
+    Ok(())
+}
+
+/// ゴールしていないプレイヤーの中で最も後ろにいるかどうかを各プレイヤーに記録する
+///
+/// 最後尾に複数のプレイヤーが並んでいる場合は全員を最後尾とみなす。
+fn update_last_place(player_status_table: &mut HashMap<String, PlayerStatus>) {
+    let last_position = player_status_table
+        .values()
+        .filter(|player_status| player_status.order_of_arrival().is_none())
+        .map(|player_status| player_status.position())
+        .min();
+    for player_status in player_status_table.values_mut() {
+        let is_in_last_place = player_status.order_of_arrival().is_none()
+            && Some(player_status.position()) == last_position;
+        player_status.set_in_last_place(is_in_last_place);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game_system::player_status::{
+        diff_player_status_table, player_status_table_from_position_list,
+    };
+    use crate::game_system::toml_interface::{world_from_effect_list, world_from_str};
+
+    /// 効果のないマスが10マス並ぶ盤面
+    fn plain_world() -> World {
+        world_from_effect_list(6, &[&[] as &[&str]; 10])
+    }
+
+    /// 効果のないマスが5マス並び、`[general]`に`setting`を追加した盤面
+    fn world_with_setting(setting: &str) -> World {
+        let mut world_toml = format!(
+            "[general]\n\
+             title = \"テスト\"\n\
+             opening_msg = \"\"\n\
+             start_description = \"スタート\"\n\
+             goal_description = \"ゴール\"\n\
+             dice_max = 6\n\
+             {}\n",
+            setting
+        );
+        for i_area in 1..=5 {
+            world_toml += &format!("[[area]]\ndescription = \"{}マス目\"\n", i_area);
+        }
+        world_from_str(&world_toml)
+    }
+
+    /// `player`が`dice`を出したときの状態の変化（訪問済みのマスの記録は除く）
+    fn diff_after_dice_roll(
+        world: &mut World,
+        player_order: &[String],
+        player_status_table: &mut HashMap<String, PlayerStatus>,
+        player: &str,
+        dice: usize,
+    ) -> Vec<String> {
+        let before = player_status_table.clone();
+        world
+            .dice_roll(
+                &Preferences::default(),
+                dice,
+                player,
+                player_order,
+                player_status_table,
+            )
+            .unwrap();
+        diff_player_status_table(&before, player_status_table)
+            .into_iter()
+            .filter(|diff| !diff.contains(".visited_area_set:"))
+            .collect()
+    }
+
+    #[test]
+    fn wait_for_pass_holds_and_consumes_turn() {
+        let (player_order, mut player_status_table) =
+            player_status_table_from_position_list(&[("Alice", 3), ("Bob", 1)]);
+        let alice_status = player_status_table.get_mut("Alice").unwrap();
+        alice_status.set_waiting_for_pass(true);
+        alice_status.set_dice_boost(1, 2);
+        alice_status.add_num_confusion(2);
+        assert_eq!(
+            diff_after_dice_roll(
+                &mut plain_world(),
+                &player_order,
+                &mut player_status_table,
+                "Alice",
+                2
+            ),
+            vec![
+                "Alice.num_dice_boost: 2 -> 1",
+                "Alice.last_dice: None -> Some(2)",
+                "Alice.num_confusion: 2 -> 1",
+            ]
+        );
+    }
+
+    #[test]
+    fn wait_for_pass_holds_until_overtaken() {
+        let (player_order, mut player_status_table) =
+            player_status_table_from_position_list(&[("Alice", 3), ("Bob", 1)]);
+        player_status_table
+            .get_mut("Alice")
+            .unwrap()
+            .set_waiting_for_pass(true);
+        // 追いついただけでは解除されない
+        diff_after_dice_roll(
+            &mut plain_world(),
+            &player_order,
+            &mut player_status_table,
+            "Bob",
+            2,
+        );
+        assert!(player_status_table["Alice"].is_waiting_for_pass());
+        assert_eq!(
+            diff_after_dice_roll(
+                &mut plain_world(),
+                &player_order,
+                &mut player_status_table,
+                "Bob",
+                1
+            ),
+            vec![
+                "Alice.is_waiting_for_pass: true -> false",
+                "Bob.position: 3 -> 4",
+                "Bob.last_dice: Some(2) -> Some(1)",
+                "Bob.is_in_last_place: true -> false",
+            ]
+        );
+    }
+
+    #[test]
+    fn wait_for_pass_releases_last_place_when_opponent_moves_ahead() {
+        let (player_order, mut player_status_table) =
+            player_status_table_from_position_list(&[("Alice", 1), ("Bob", 3)]);
+        player_status_table
+            .get_mut("Alice")
+            .unwrap()
+            .set_waiting_for_pass(true);
+        assert_eq!(
+            diff_after_dice_roll(
+                &mut plain_world(),
+                &player_order,
+                &mut player_status_table,
+                "Bob",
+                2
+            ),
+            vec![
+                "Alice.is_waiting_for_pass: true -> false",
+                "Alice.is_in_last_place: false -> true",
+                "Bob.position: 3 -> 5",
+                "Bob.last_dice: None -> Some(2)",
+            ]
+        );
+    }
+
+    #[test]
+    fn jump_edges_lists_go_to_start_and_mirror() {
+        // 0: スタート, 1..=5: マス, 6: ゴール
+        let world = world_from_effect_list(
+            3,
+            &[
+                &[],
+                &["GoToStart:"],
+                &[],
+                &["Mirror:"],
+                &["PushSelf: num = 1"],
+            ],
+        );
+        assert_eq!(world.jump_edges(), vec![(2, 0), (4, 2)]);
+    }
+
+    #[test]
+    fn can_reach_goal_flags_trap_board() {
+        let world = world_from_effect_list(1, &[&["PullSelf: num = 1"], &[]]);
+        assert!(!world.can_reach_goal().unwrap());
+        let world = world_from_effect_list(1, &[&["SkipSelf: times = 1"], &[]]);
+        assert!(world.can_reach_goal().unwrap());
+    }
+
+    #[test]
+    fn boost_dice_applies_and_expires() {
+        let mut effect_list = vec![&[] as &[&str]; 15];
+        effect_list[0] = &["BoostDice: delta = 2, turns = 2"];
+        let mut world = world_from_effect_list(3, &effect_list);
+        let (player_order, mut player_status_table) =
+            player_status_table_from_position_list(&[("Alice", 0)]);
+        diff_after_dice_roll(
+            &mut world,
+            &player_order,
+            &mut player_status_table,
+            "Alice",
+            1,
+        );
+        assert_eq!(world.player_dice_max(&player_status_table["Alice"]), 5);
+        diff_after_dice_roll(
+            &mut world,
+            &player_order,
+            &mut player_status_table,
+            "Alice",
+            5,
+        );
+        assert_eq!(world.player_dice_max(&player_status_table["Alice"]), 5);
+        diff_after_dice_roll(
+            &mut world,
+            &player_order,
+            &mut player_status_table,
+            "Alice",
+            5,
+        );
+        assert_eq!(world.player_dice_max(&player_status_table["Alice"]), 3);
+        assert!(matches!(
+            world.dice_roll(
+                &Preferences::default(),
+                4,
+                "Alice",
+                &player_order,
+                &mut player_status_table,
+            ),
+            Err(GameSystemError::OutOfRangeDice(4))
+        ));
+    }
+
+    #[test]
+    fn arrival_points_follow_order_and_overflow_gets_zero() {
+        let mut world = world_with_setting("arrival_points = [10, 5]");
+        let (player_order, mut player_status_table) =
+            player_status_table_from_position_list(&[("Alice", 4), ("Bob", 3), ("Carol", 2)]);
+        for (player, dice) in [("Bob", 3), ("Alice", 2), ("Carol", 4)] {
+            diff_after_dice_roll(
+                &mut world,
+                &player_order,
+                &mut player_status_table,
+                player,
+                dice,
+            );
+        }
+        assert_eq!(player_status_table["Bob"].point(), 10);
+        assert_eq!(player_status_table["Alice"].point(), 5);
+        assert_eq!(player_status_table["Carol"].point(), 0);
+        assert_eq!(player_status_table["Carol"].order_of_arrival(), Some(3));
+    }
+
+    /// `collision`の盤面で、Aliceが1マス目からBobのいる3マス目に止まった後の二人の位置
+    fn position_after_collision(collision: &str) -> (usize, usize) {
+        let mut world = world_with_setting(&format!("collision = \"{}\"", collision));
+        let (player_order, mut player_status_table) =
+            player_status_table_from_position_list(&[("Alice", 1), ("Bob", 3)]);
+        diff_after_dice_roll(
+            &mut world,
+            &player_order,
+            &mut player_status_table,
+            "Alice",
+            2,
+        );
+        (
+            player_status_table["Alice"].position(),
+            player_status_table["Bob"].position(),
+        )
+    }
+
+    #[test]
+    fn collision_modes_with_two_players() {
+        assert_eq!(position_after_collision("stack"), (3, 3));
+        assert_eq!(position_after_collision("bump_forward"), (4, 3));
+        assert_eq!(position_after_collision("swap"), (3, 1));
+    }
+
+    #[test]
+    fn goal_tiers_give_different_bonuses() {
+        let mut world = world_from_str(
+            r#"
+            [general]
+            title = "テスト"
+            opening_msg = ""
+            start_description = "スタート"
+            goal_description = "ゴール"
+            dice_max = 6
+            goal_bonus = 10
+
+            [[area]]
+            description = "1マス目"
+            [[area]]
+            description = "途中のゴール"
+            goal_bonus = 3
+            [[area]]
+            description = "3マス目"
+            "#,
+        );
+        let (player_order, mut player_status_table) =
+            player_status_table_from_position_list(&[("Alice", 0), ("Bob", 0)]);
+        diff_after_dice_roll(
+            &mut world,
+            &player_order,
+            &mut player_status_table,
+            "Alice",
+            2,
+        );
+        diff_after_dice_roll(
+            &mut world,
+            &player_order,
+            &mut player_status_table,
+            "Bob",
+            4,
+        );
+        assert_eq!(player_status_table["Alice"].order_of_arrival(), Some(1));
+        assert_eq!(player_status_table["Alice"].point(), 3);
+        assert_eq!(player_status_table["Bob"].order_of_arrival(), Some(2));
+        assert_eq!(player_status_table["Bob"].point(), 10);
+    }
+
+    #[test]
+    fn reverse_goal_is_reached_at_original_start() {
+        let mut world = world_with_setting("reverse_goal = true");
+        assert_eq!(world.area_list()[0].description(), "ゴール");
+        assert_eq!(world.area_list()[1].description(), "5マス目");
+        let (player_order, mut player_status_table) =
+            player_status_table_from_position_list(&[("Alice", 0), ("Bob", 0)]);
+        diff_after_dice_roll(
+            &mut world,
+            &player_order,
+            &mut player_status_table,
+            "Alice",
+            5,
+        );
+        assert_eq!(player_status_table["Alice"].order_of_arrival(), None);
+        diff_after_dice_roll(
+            &mut world,
+            &player_order,
+            &mut player_status_table,
+            "Alice",
+            1,
+        );
+        let alice_status = &player_status_table["Alice"];
+        assert_eq!(alice_status.order_of_arrival(), Some(1));
+        assert_eq!(
+            world.area_list()[alice_status.position()].description(),
+            "スタート"
+        );
+    }
+
+    #[test]
+    fn oversized_dice_max_warns_and_clamp_limits_rolls() {
+        let preferences = Preferences::default();
+        let world = world_from_effect_list(4, &[&[], &[], &[], &[], &[]]);
+        assert!(world.warning_list(&preferences).is_empty());
+        let mut world = world_from_effect_list(10, &[&[], &[], &[], &[], &[]]);
+        assert_eq!(world.warning_list(&preferences).len(), 1);
+        assert_eq!(world.player_dice_max(&PlayerStatus::default()), 10);
+        let (player_order, mut player_status_table) =
+            player_status_table_from_position_list(&[("Alice", 0)]);
+        assert!(world
+            .dice_roll(
+                &preferences,
+                10,
+                "Alice",
+                &player_order,
+                &mut player_status_table
+            )
+            .is_ok());
+
+        let mut world = world_from_str(
+            r#"
+            [general]
+            title = "テスト"
+            opening_msg = ""
+            start_description = "スタート"
+            goal_description = "ゴール"
+            dice_max = 10
+            clamp_dice_max = true
+
+            [[area]]
+            description = "1マス目"
+            [[area]]
+            description = "2マス目"
+            "#,
+        );
+        assert_eq!(world.warning_list(&preferences).len(), 1);
+        assert_eq!(world.player_dice_max(&PlayerStatus::default()), 2);
+        let (player_order, mut player_status_table) =
+            player_status_table_from_position_list(&[("Alice", 0)]);
+        assert!(matches!(
+            world.dice_roll(
+                &preferences,
+                3,
+                "Alice",
+                &player_order,
+                &mut player_status_table
+            ),
+            Err(GameSystemError::OutOfRangeDice(3))
+        ));
+        // 制限された最大値を出してもスタートから一回ではゴールできない
+        world
+            .dice_roll(
+                &preferences,
+                2,
+                "Alice",
+                &player_order,
+                &mut player_status_table,
+            )
+            .unwrap();
+        assert_eq!(player_status_table["Alice"].order_of_arrival(), None);
+    }
+
+    #[test]
+    fn banked_dice_is_used_exactly_once() {
+        let mut effect_list = vec![&[] as &[&str]; 12];
+        effect_list[1] = &["BankRoll:"];
+        effect_list[4] = &["UseBank:"];
+        effect_list[7] = &["UseBank:"];
+        let mut world = world_from_effect_list(6, &effect_list);
+        let (player_order, mut player_status_table) =
+            player_status_table_from_position_list(&[("Alice", 0)]);
+        assert_eq!(
+            diff_after_dice_roll(
+                &mut world,
+                &player_order,
+                &mut player_status_table,
+                "Alice",
+                2
+            ),
+            vec![
+                "Alice.position: 0 -> 2",
+                "Alice.last_dice: None -> Some(2)",
+                "Alice.banked_dice: None -> Some(2)",
+                "Alice.is_in_last_place: false -> true",
+            ]
+        );
+        assert_eq!(
+            diff_after_dice_roll(
+                &mut world,
+                &player_order,
+                &mut player_status_table,
+                "Alice",
+                3
+            ),
+            vec![
+                "Alice.position: 2 -> 7",
+                "Alice.last_dice: Some(2) -> Some(3)",
+                "Alice.banked_dice: Some(2) -> None",
+            ]
+        );
+        assert_eq!(
+            diff_after_dice_roll(
+                &mut world,
+                &player_order,
+                &mut player_status_table,
+                "Alice",
+                1
+            ),
+            vec![
+                "Alice.position: 7 -> 8",
+                "Alice.last_dice: Some(3) -> Some(1)",
+            ]
+        );
+    }
+
+    #[test]
+    fn show_map_lists_upcoming_areas_with_effects() {
+        let mut world =
+            world_from_effect_list(6, &[&[], &["ShowMap:"], &[], &["PushSelf: num = 1"], &[]]);
+        let (player_order, mut player_status_table) =
+            player_status_table_from_position_list(&[("Alice", 0)]);
+        let text = world
+            .dice_roll(
+                &Preferences::default(),
+                2,
+                "Alice",
+                &player_order,
+                &mut player_status_table,
+            )
+            .unwrap();
+        let (_, map_text) = text.split_once("地図\n").unwrap();
+        assert_eq!(
+            map_text.lines().collect::<Vec<_>>(),
+            vec![
+                "  3: 3マス目 / なし",
+                "  4: 4マス目 / プレイヤーは1 マス進む。",
+                "  5: 5マス目 / なし",
+                "  6: ゴール / なし",
+            ]
+        );
+    }
+
+    #[test]
+    fn confused_player_moves_backward_then_reverts() {
+        let mut effect_list = vec![&[] as &[&str]; 10];
+        effect_list[3] = &["Confuse: turns = 1"];
+        let mut world = world_from_effect_list(6, &effect_list);
+        let (player_order, mut player_status_table) =
+            player_status_table_from_position_list(&[("Alice", 0)]);
+        let mut position_list = Vec::new();
+        for dice in [4, 2, 3] {
+            diff_after_dice_roll(
+                &mut world,
+                &player_order,
+                &mut player_status_table,
+                "Alice",
+                dice,
+            );
+            position_list.push(player_status_table["Alice"].position());
+        }
+        assert_eq!(position_list, vec![4, 2, 5]);
+    }
+
+    #[test]
+    fn desperation_bonus_only_in_last_place() {
+        let mut effect_list = vec![&[] as &[&str]; 12];
+        effect_list[0] = &["Desperation:"];
+        let mut world = world_from_effect_list(6, &effect_list);
+        let (player_order, mut player_status_table) =
+            player_status_table_from_position_list(&[("Alice", 0), ("Bob", 3)]);
+        let mut dice_max_list = Vec::new();
+        for (player, dice) in [("Alice", 1), ("Alice", 4), ("Bob", 4)] {
+            diff_after_dice_roll(
+                &mut world,
+                &player_order,
+                &mut player_status_table,
+                player,
+                dice,
+            );
+            dice_max_list.push(world.player_dice_max(&player_status_table["Alice"]));
+        }
+        assert_eq!(dice_max_list, vec![7, 6, 7]);
+    }
+
+    /// 1マス目でフラグを立て、2マス目でフラグが立っていれば5マス目へ移動する盤面
+    fn flag_world() -> World {
+        let mut world_toml = r#"
+            [general]
+            title = "テスト"
+            opening_msg = ""
+            start_description = "スタート"
+            goal_description = "ゴール"
+            dice_max = 6
+
+            [[area]]
+            description = "1マス目"
+            [[area.effect]]
+            element = 'SetFlag: name = "gate_open"'
+
+            [[area]]
+            description = "2マス目"
+            [[area.effect]]
+            element = 'GotoLabelIfFlag: flag = "gate_open", label = "shortcut"'
+            "#
+        .to_string();
+        for i_area in 3..=8 {
+            world_toml += &format!("[[area]]\ndescription = \"{}マス目\"\n", i_area);
+            if i_area == 5 {
+                world_toml += "label = \"shortcut\"\n";
+            }
+        }
+        world_from_str(&world_toml)
+    }
+
+    #[test]
+    fn set_flag_enables_goto_label() {
+        let mut world = flag_world();
+        let (player_order, mut player_status_table) =
+            player_status_table_from_position_list(&[("Alice", 0)]);
+        diff_after_dice_roll(
+            &mut world,
+            &player_order,
+            &mut player_status_table,
+            "Alice",
+            1,
+        );
+        assert_eq!(world.flag_table().get("gate_open"), Some(&true));
+        assert_eq!(
+            diff_after_dice_roll(
+                &mut world,
+                &player_order,
+                &mut player_status_table,
+                "Alice",
+                1
+            ),
+            vec!["Alice.position: 1 -> 5"]
+        );
+        world.reset(&mut player_status_table);
+        assert!(world.flag_table().is_empty());
+    }
+
+    #[test]
+    fn unset_flag_does_not_goto_label() {
+        let mut world = flag_world();
+        let (player_order, mut player_status_table) =
+            player_status_table_from_position_list(&[("Alice", 0)]);
+        diff_after_dice_roll(
+            &mut world,
+            &player_order,
+            &mut player_status_table,
+            "Alice",
+            2,
+        );
+        assert!(world.flag_table().is_empty());
+        assert_eq!(player_status_table["Alice"].position(), 2);
+    }
+}
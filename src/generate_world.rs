@@ -0,0 +1,22 @@
+// Copyright (c) 2023 Yuichi Ishida
+//
+// Released under the MIT license.
+// see https://opensource.org/licenses/mit-license.php
+
+use crate::game_system::toml_interface::generate_world_toml;
+use anyhow::{Context, Result};
+use std::fs;
+use std::path::PathBuf;
+
+pub fn run(
+    num_area: usize,
+    dice_max: usize,
+    output_file_path: PathBuf,
+    seed: Option<u64>,
+) -> Result<()> {
+    let seed = seed.unwrap_or_else(rand::random);
+    let world_toml = generate_world_toml(num_area, dice_max, seed)?;
+    fs::write(&output_file_path, world_toml)
+        .with_context(|| format!("failed to write {}", output_file_path.display()))?;
+    Ok(())
+}
@@ -3,9 +3,14 @@
 // Released under the MIT license.
 // see https://opensource.org/licenses/mit-license.php
 
+mod check_world;
 pub mod cli;
 mod error;
 mod game_system;
+mod generate_world;
+mod list_effects;
 mod preferences;
+mod show_players;
 mod user_interface;
+mod world_to_csv;
 mod world_to_tex;
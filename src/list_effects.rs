@@ -0,0 +1,25 @@
+// Copyright (c) 2023 Yuichi Ishida
+//
+// Released under the MIT license.
+// see https://opensource.org/licenses/mit-license.php
+
+use crate::game_system::area::area_effect_catalog;
+use crate::preferences::{Language, Preferences};
+use anyhow::Result;
+use std::io::{self, BufWriter, Write};
+
+pub fn run() -> Result<()> {
+    let preferences: Preferences = Default::default();
+    let mut buf_writer = BufWriter::new(io::stdout().lock());
+    for (effect_name, input_format, area_effect) in area_effect_catalog() {
+        writeln!(buf_writer, "{}", effect_name)?;
+        match preferences.language() {
+            Language::Japanese => writeln!(buf_writer, "  入力形式: {}", input_format)?,
+        }
+        for line in area_effect.help_text(&preferences).lines() {
+            writeln!(buf_writer, "  {}", line)?;
+        }
+        writeln!(buf_writer)?;
+    }
+    Ok(())
+}
@@ -3,28 +3,51 @@
 // Released under the MIT license.
 // see https://opensource.org/licenses/mit-license.php
 
-#[derive(Clone, Copy, Debug, Default)]
+use std::time::Duration;
+
+#[derive(Clone, Copy, Debug)]
 pub struct Preferences {
     language: Language,
+    auto_advance_delay: Option<Duration>,
+    reveal_effects: bool,
+}
+
+impl Default for Preferences {
+    fn default() -> Self {
+        Self {
+            language: Default::default(),
+            auto_advance_delay: None,
+            reveal_effects: true,
+        }
+    }
 }
 
 impl Preferences {
     pub fn language(&self) -> Language {
         self.language
     }
+    /// サイコロの結果画面から自動で次へ進むまでの時間（`None`の場合は自動で進まない）
+    pub fn auto_advance_delay(&self) -> Option<Duration> {
+        self.auto_advance_delay
+    }
+    pub fn set_auto_advance_delay(&mut self, auto_advance_delay: Option<Duration>) {
+        self.auto_advance_delay = auto_advance_delay;
+    }
+    /// サイコロを振る前にマスの効果を表示するかどうか（`false`の場合は止まるまで効果を伏せる）
+    pub fn reveal_effects(&self) -> bool {
+        self.reveal_effects
+    }
+    pub fn set_reveal_effects(&mut self, reveal_effects: bool) {
+        self.reveal_effects = reveal_effects;
+    }
 }
 
-#[derive(Clone, Copy, Debug)]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
 pub enum Language {
+    #[default]
     Japanese,
 }
 
-impl Default for Language {
-    fn default() -> Self {
-        Self::Japanese
-    }
-}
-
 // #[derive(Clone, Copy, Debug, Default)]
 // struct TuiPreferences {
 //     player_list_window_width: u16,
@@ -0,0 +1,87 @@
+// Copyright (c) 2023 Yuichi Ishida
+//
+// Released under the MIT license.
+// see https://opensource.org/licenses/mit-license.php
+
+use crate::error::GameSystemError;
+use crate::game_system::toml_interface::{
+    read_player_list_from_file, read_player_list_meta_from_file,
+};
+use crate::preferences::{Language, Preferences};
+use anyhow::Result;
+use std::io::{self, BufWriter, Write};
+use std::path::{Path, PathBuf};
+
+pub fn run(player_list_file_path: PathBuf) -> Result<()> {
+    let preferences: Preferences = Default::default();
+    let mut buf_writer = BufWriter::new(io::stdout().lock());
+    write_players(&mut buf_writer, &preferences, &player_list_file_path)
+}
+
+/// プレイヤーファイルの覚え書きと各プレイヤーの名前、自己紹介を書き出す
+fn write_players(
+    buf_writer: &mut impl Write,
+    preferences: &Preferences,
+    player_list_file_path: &Path,
+) -> Result<()> {
+    let (player_order, player_status_table) = read_player_list_from_file(player_list_file_path)?;
+    let meta = read_player_list_meta_from_file(player_list_file_path)?;
+    match preferences.language() {
+        Language::Japanese => {
+            if let Some(description) = meta.description() {
+                writeln!(buf_writer, "説明: {}", description)?;
+            }
+            if let Some(created) = meta.created() {
+                writeln!(buf_writer, "作成日: {}", created)?;
+            }
+            if meta.description().is_some() || meta.created().is_some() {
+                writeln!(buf_writer)?;
+            }
+            for player in player_order.iter() {
+                let player_status = player_status_table
+                    .get(player)
+                    .ok_or_else(|| GameSystemError::NotFoundPlayer(player.to_owned()))?;
+                writeln!(buf_writer, "{}", player)?;
+                if let Some(intro) = player_status.intro() {
+                    writeln!(buf_writer, "  一言: {}", intro)?;
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    #[test]
+    fn write_players_prints_names_and_intros() {
+        let player_list_file_path =
+            std::env::temp_dir().join(format!("sugoroku_show_players_{}.toml", std::process::id()));
+        fs::write(
+            &player_list_file_path,
+            r#"
+            [meta]
+            description = "テスト用"
+
+            [[player]]
+            name = "Alice"
+            intro = "よろしく"
+
+            [[player]]
+            name = "Bob"
+            "#,
+        )
+        .unwrap();
+        let mut output = Vec::new();
+        let result = write_players(&mut output, &Preferences::default(), &player_list_file_path);
+        fs::remove_file(&player_list_file_path).unwrap();
+        result.unwrap();
+        assert_eq!(
+            String::from_utf8(output).unwrap(),
+            "説明: テスト用\n\nAlice\n  一言: よろしく\nBob\n"
+        );
+    }
+}
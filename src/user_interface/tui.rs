@@ -13,31 +13,129 @@ use crate::user_interface::tui::status::GameData;
 use anyhow::Result;
 use std::io;
 use std::path::PathBuf;
+use std::thread;
+use std::time::{Duration, Instant};
 use termion;
 use termion::input::TermRead;
 use termion::raw::IntoRawMode;
-use tui::backend::TermionBackend;
+use tui::backend::{Backend, TermionBackend};
+use tui::layout::Rect;
 use tui::terminal::Terminal;
 
+const POLLING_INTERVAL: Duration = Duration::from_millis(20);
+
 pub fn run(
     preferences: Preferences,
     player_list_file_path: PathBuf,
     world_file_path: PathBuf,
+    trace_ui_file_path: Option<PathBuf>,
+    practice: bool,
 ) -> Result<()> {
     let (player_order, player_status_table) = read_player_list_from_file(&player_list_file_path)?;
     let world = read_world_from_file(&world_file_path)?;
+    let mut game_data = GameData::try_new(world, player_order, player_status_table)?;
+    if practice {
+        game_data.enable_practice_mode()?;
+    }
     let stdout = termion::screen::AlternateScreen::from(io::stdout().into_raw_mode()?);
     let backend = TermionBackend::new(stdout);
     let mut terminal = Terminal::new(backend)?;
-    let mut game_data = GameData::try_new(world, player_order, player_status_table)?;
+    if trace_ui_file_path.is_some() {
+        game_data.enable_transition_log();
+    }
     game_data.init(&preferences)?;
+    let result = event_loop(&mut terminal, &preferences, &mut game_data);
+    drop(terminal);
+    let write_result = match trace_ui_file_path {
+        Some(trace_ui_file_path) => game_data.write_transition_log(&trace_ui_file_path),
+        None => Ok(()),
+    };
+    // ゲーム中のエラーを優先して返し、遷移ログの書き込みに失敗したことは表示だけしておく
+    if let (Err(_), Err(write_error)) = (&result, &write_result) {
+        eprintln!("UIの遷移ログを書き込めませんでした: {:#}", write_error);
+    }
+    result.and(write_result)
+}
+
+fn event_loop<B: Backend>(
+    terminal: &mut Terminal<B>,
+    preferences: &Preferences,
+    game_data: &mut GameData,
+) -> Result<()> {
     terminal.hide_cursor()?;
-    terminal.draw(|frame| ui(frame, &preferences, &game_data))?;
-    while let Some(Ok(key)) = io::stdin().keys().next() {
-        if game_data.transition(&preferences, key)? {
-            break;
+    terminal.draw(|frame| ui(frame, preferences, game_data))?;
+    let mut terminal_size = terminal.size()?;
+    let mut keys = termion::async_stdin().keys();
+    loop {
+        match keys.next() {
+            Some(Ok(key)) => {
+                if game_data.transition(preferences, key)? {
+                    break;
+                }
+                terminal.draw(|frame| ui(frame, preferences, game_data))?;
+            }
+            Some(Err(_)) => break,
+            None => {
+                let is_ticked = game_data.tick(preferences, Instant::now())?;
+                if needs_redraw(is_ticked, terminal_size, terminal.size()?) {
+                    terminal.draw(|frame| ui(frame, preferences, game_data))?;
+                    terminal_size = terminal.size()?;
+                }
+                thread::sleep(POLLING_INTERVAL);
+            }
         }
-        terminal.draw(|frame| ui(frame, &preferences, &game_data))?;
     }
     Ok(())
 }
+
+/// 時間経過で状態が変わったか、前回の描画から端末の大きさが変わったときに再描画する
+fn needs_redraw(is_ticked: bool, previous_size: Rect, current_size: Rect) -> bool {
+    is_ticked || previous_size != current_size
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::user_interface::tui::screen::{buffer_to_line_list, find_label};
+    use crate::user_interface::tui::status::game_data_from_effect_list;
+    use tui::backend::TestBackend;
+
+    #[test]
+    fn needs_redraw_on_tick_or_resize() {
+        let size = Rect::new(0, 0, 120, 40);
+        assert!(!needs_redraw(false, size, size));
+        assert!(needs_redraw(true, size, size));
+        assert!(needs_redraw(false, size, Rect::new(0, 0, 80, 40)));
+        assert!(needs_redraw(false, size, Rect::new(0, 0, 120, 30)));
+    }
+
+    #[test]
+    fn redraw_after_resize_follows_new_size() {
+        let preferences = Preferences::default();
+        let game_data = game_data_from_effect_list(&preferences, &[&[]], &[("Alice", 0)]);
+        let mut terminal = Terminal::new(TestBackend::new(120, 40)).unwrap();
+        terminal
+            .draw(|frame| ui(frame, &preferences, &game_data))
+            .unwrap();
+        let previous_size = terminal.size().unwrap();
+        let before = buffer_to_line_list(terminal.backend().buffer());
+
+        terminal.backend_mut().resize(80, 30);
+        let current_size = terminal.size().unwrap();
+        assert!(needs_redraw(false, previous_size, current_size));
+        terminal
+            .draw(|frame| ui(frame, &preferences, &game_data))
+            .unwrap();
+        let after = buffer_to_line_list(terminal.backend().buffer());
+
+        assert_eq!(after.len(), 30);
+        assert_ne!(
+            find_label(&before, "テスト"),
+            find_label(&after, "テスト"),
+            "before:\n{}\nafter:\n{}",
+            before.join("\n"),
+            after.join("\n")
+        );
+        assert!(find_label(&after, "テスト").is_some());
+    }
+}
@@ -8,9 +8,16 @@ use crate::user_interface::tui::status::{GameData, UiStatus};
 use tui::backend::Backend;
 use tui::layout::{Alignment, Constraint, Direction, Layout};
 use tui::terminal::Frame;
-use tui::widgets::{Block, Borders, Paragraph};
+use tui::widgets::{Block, Borders, Paragraph, Wrap};
+
+const MIN_WIDTH: u16 = 40;
+const MIN_HEIGHT: u16 = 12;
 
 pub fn ui<B: Backend>(frame: &mut Frame<B>, preferences: &Preferences, game_data: &GameData) {
+    if frame.size().width < MIN_WIDTH || frame.size().height < MIN_HEIGHT {
+        ui_too_small(frame, preferences);
+        return;
+    }
     match game_data.ui_status {
         UiStatus::TitleMenu => ui_title(frame, preferences, game_data),
         UiStatus::QuitMenu => {
@@ -38,6 +45,23 @@ fn ui_title<B: Backend>(frame: &mut Frame<B>, preferences: &Preferences, game_da
     opening_msg_text.push_str(game_data.world.opening_msg());
     opening_msg_text.push('\n');
     opening_msg_text.push('\n');
+    let mut exists_intro = false;
+    for player in game_data.player_order.iter() {
+        if let Some(intro) = game_data
+            .player_status_table
+            .get(player)
+            .and_then(|player_status| player_status.intro())
+        {
+            opening_msg_text.push_str(player);
+            opening_msg_text.push_str(": ");
+            opening_msg_text.push_str(intro);
+            opening_msg_text.push('\n');
+            exists_intro = true;
+        }
+    }
+    if exists_intro {
+        opening_msg_text.push('\n');
+    }
     match game_data.ui_status_buffer {
         UiStatus::TitleMenu => {
             opening_msg_text.push_str(match preferences.language() {
@@ -112,3 +136,221 @@ fn ui_quit<B: Backend>(frame: &mut Frame<B>, preferences: &Preferences) {
         .block(Block::default());
     frame.render_widget(opening_msg, chunks[2]);
 }
+
+fn ui_too_small<B: Backend>(frame: &mut Frame<B>, preferences: &Preferences) {
+    let text = match preferences.language() {
+        Language::Japanese => format!(
+            "端末の大きさが足りません。\n{}x{} 以上にしてください。",
+            MIN_WIDTH, MIN_HEIGHT
+        ),
+    };
+    frame.render_widget(
+        Paragraph::new(text)
+            .alignment(Alignment::Center)
+            .wrap(Wrap { trim: true }),
+        frame.size(),
+    );
+}
+
+/// テスト用に、`width`x`height`の画面に描画した結果を一行ずつの文字列にする
+///
+/// 全角文字の後ろの隠れたセルは飛ばすため、各行の文字列は画面の見た目と同じ並びになる。
+#[cfg(test)]
+pub fn render_to_line_list(
+    preferences: &Preferences,
+    game_data: &GameData,
+    width: u16,
+    height: u16,
+) -> Vec<String> {
+    use tui::backend::TestBackend;
+    use tui::terminal::Terminal;
+    let mut terminal = Terminal::new(TestBackend::new(width, height)).unwrap();
+    terminal
+        .draw(|frame| ui(frame, preferences, game_data))
+        .unwrap();
+    buffer_to_line_list(terminal.backend().buffer())
+}
+
+/// テスト用に、描画済みのバッファを`render_to_line_list`と同じ形の文字列の一覧にする
+#[cfg(test)]
+pub fn buffer_to_line_list(buffer: &tui::buffer::Buffer) -> Vec<String> {
+    use unicode_width::UnicodeWidthStr;
+    let mut line_list = Vec::with_capacity(buffer.area.height as usize);
+    for y in 0..buffer.area.height {
+        let mut line = String::new();
+        let mut x = 0;
+        while x < buffer.area.width {
+            let symbol = &buffer.get(x, y).symbol;
+            line.push_str(symbol);
+            x += (symbol.width() as u16).max(1);
+        }
+        line_list.push(line);
+    }
+    line_list
+}
+
+/// テスト用に、描画結果の中で`label`が最初に現れる位置を`(列, 行)`として返す（列は表示幅で数える）
+#[cfg(test)]
+pub fn find_label(line_list: &[String], label: &str) -> Option<(usize, usize)> {
+    use unicode_width::UnicodeWidthStr;
+    line_list.iter().enumerate().find_map(|(y, line)| {
+        line.find(label)
+            .map(|byte_index| (line[..byte_index].width(), y))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::user_interface::tui::status::game_data_from_effect_list;
+    use termion::event::Key;
+
+    const WIDTH: u16 = 120;
+    const HEIGHT: u16 = 40;
+
+    /// 二人のプレイヤーがスタートにいる、三マス先がゴールの盤面
+    fn fixed_game_data(preferences: &Preferences) -> GameData {
+        game_data_from_effect_list(
+            preferences,
+            &[&[], &["PushSelf: num = 1"]],
+            &[("Alice", 0), ("Bob", 0)],
+        )
+    }
+
+    fn transition_all(preferences: &Preferences, game_data: &mut GameData, key_list: &[Key]) {
+        for &key in key_list {
+            game_data.transition(preferences, key).unwrap();
+        }
+    }
+
+    fn assert_label_list(line_list: &[String], expected_list: &[(&str, (usize, usize))]) {
+        for &(label, position) in expected_list {
+            assert_eq!(
+                find_label(line_list, label),
+                Some(position),
+                "label `{}` in\n{}",
+                label,
+                line_list.join("\n")
+            );
+        }
+    }
+
+    #[test]
+    fn title_screen() {
+        let preferences = Preferences::default();
+        let game_data = fixed_game_data(&preferences);
+        let line_list = render_to_line_list(&preferences, &game_data, WIDTH, HEIGHT);
+        assert_label_list(
+            &line_list,
+            &[
+                ("テスト", (57, 16)),
+                ("開始するにはエンターキーを押してください。", (39, 22)),
+            ],
+        );
+    }
+
+    #[test]
+    fn title_screen_shows_intros() {
+        let preferences = Preferences::default();
+        let mut game_data = fixed_game_data(&preferences);
+        game_data
+            .player_status_table
+            .get_mut("Bob")
+            .unwrap()
+            .set_intro(Some("よろしく".to_string()));
+        let line_list = render_to_line_list(&preferences, &game_data, WIDTH, HEIGHT);
+        assert_label_list(
+            &line_list,
+            &[
+                ("Bob: よろしく", (54, 22)),
+                ("開始するにはエンターキーを押してください。", (39, 24)),
+            ],
+        );
+        assert_eq!(find_label(&line_list, "Alice:"), None);
+    }
+
+    #[test]
+    fn title_screen_without_intros() {
+        let preferences = Preferences::default();
+        let game_data = fixed_game_data(&preferences);
+        let line_list = render_to_line_list(&preferences, &game_data, WIDTH, HEIGHT);
+        assert_eq!(find_label(&line_list, "Alice:"), None);
+        assert_eq!(find_label(&line_list, "Bob:"), None);
+    }
+
+    #[test]
+    fn playing_screen() {
+        let preferences = Preferences::default();
+        let mut game_data = fixed_game_data(&preferences);
+        transition_all(
+            &preferences,
+            &mut game_data,
+            &[Key::Char('\n'), Key::Char('2')],
+        );
+        let line_list = render_to_line_list(&preferences, &game_data, WIDTH, HEIGHT);
+        assert_label_list(
+            &line_list,
+            &[
+                ("Guidance", (1, 0)),
+                ("Player lis", (1, 4)),
+                ("Message", (14, 4)),
+                ("サイコロを振ってください（最大値: 3）>> 2", (14, 5)),
+                ("🎲 Alic", (4, 6)),
+                ("Bob", (7, 7)),
+                ("スタート", (14, 8)),
+            ],
+        );
+    }
+
+    #[test]
+    fn quit_screen() {
+        let preferences = Preferences::default();
+        let mut game_data = fixed_game_data(&preferences);
+        transition_all(&preferences, &mut game_data, &[Key::Char('\n'), Key::Esc]);
+        let line_list = render_to_line_list(&preferences, &game_data, WIDTH, HEIGHT);
+        assert_label_list(
+            &line_list,
+            &[("ゲームを終了しますか？", (49, 16)), ("Y / [n]", (57, 19))],
+        );
+    }
+
+    #[test]
+    fn finished_screen() {
+        let preferences = Preferences::default();
+        let mut game_data = fixed_game_data(&preferences);
+        transition_all(
+            &preferences,
+            &mut game_data,
+            &[
+                Key::Char('\n'),
+                Key::Char('3'),
+                Key::Char('\n'),
+                Key::Char('\n'),
+                Key::Char('3'),
+                Key::Char('\n'),
+                Key::Char('\n'),
+            ],
+        );
+        let line_list = render_to_line_list(&preferences, &game_data, WIDTH, HEIGHT);
+        assert_label_list(
+            &line_list,
+            &[
+                ("全員ゴールしました。", (14, 5)),
+                (" 1    Alic", (1, 6)),
+                (" 2 🎲 Bob", (1, 7)),
+            ],
+        );
+    }
+
+    #[test]
+    fn too_small_screen_shows_guard() {
+        let preferences = Preferences::default();
+        let game_data = fixed_game_data(&preferences);
+        let line_list = render_to_line_list(&preferences, &game_data, MIN_WIDTH - 1, MIN_HEIGHT);
+        assert!(find_label(&line_list, "端末の大きさが足りません。").is_some());
+        assert_eq!(find_label(&line_list, "テスト"), None);
+        let line_list = render_to_line_list(&preferences, &game_data, MIN_WIDTH, MIN_HEIGHT);
+        assert_eq!(find_label(&line_list, "端末の大きさが足りません。"), None);
+        assert!(find_label(&line_list, "テスト").is_some());
+    }
+}
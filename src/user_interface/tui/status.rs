@@ -4,13 +4,18 @@
 // see https://opensource.org/licenses/mit-license.php
 
 use crate::error::GameSystemError;
+use crate::game_system::area::Area;
 use crate::game_system::player_status::PlayerOrder;
 use crate::game_system::player_status::PlayerStatus;
 use crate::game_system::world::World;
 use crate::preferences::{Language, Preferences};
-use anyhow::Result;
+use anyhow::{Context, Result};
 use std::collections::HashMap;
-use std::fmt::Write as _;
+use std::fmt::{self, Write as _};
+use std::fs::File;
+use std::io::{BufWriter, Write as _};
+use std::path::Path;
+use std::time::Instant;
 use termion;
 use termion::event::Key;
 
@@ -23,6 +28,16 @@ pub struct GameData {
     pub ui_status: UiStatus,
     pub ui_status_buffer: UiStatus,
     pub text_set: TextSet,
+    pub dice_result_shown_at: Option<Instant>,
+    pub transition_log: Option<Vec<(UiStatus, Option<Key>, UiStatus)>>,
+    pub practice_record: Option<PracticeRecord>,
+}
+
+/// 練習モードでの手数の記録
+#[derive(Clone, Copy, Debug, Default)]
+pub struct PracticeRecord {
+    pub num_turn: u32,
+    pub best_num_turn: Option<u32>,
 }
 
 #[derive(Clone, Debug)]
@@ -35,6 +50,19 @@ pub enum UiStatus {
     GameFinished,
 }
 
+impl fmt::Display for UiStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            UiStatus::QuitMenu => "QuitMenu",
+            UiStatus::TitleMenu => "TitleMenu",
+            UiStatus::DiceRoll => "DiceRoll",
+            UiStatus::Skip => "Skip",
+            UiStatus::DiceResult => "DiceResult",
+            UiStatus::GameFinished => "GameFinished",
+        })
+    }
+}
+
 #[derive(Clone, Debug, Default)]
 pub struct TextSet {
     pub main_window: String,
@@ -54,6 +82,11 @@ impl GameData {
             .first()
             .ok_or(GameSystemError::NoPlayer)?
             .to_owned();
+        if player_order.len() < world.min_num_player() {
+            return Err(
+                GameSystemError::TooFewPlayers(world.min_num_player(), player_order.len()).into(),
+            );
+        }
         Ok(Self {
             world,
             current_player,
@@ -62,22 +95,87 @@ impl GameData {
             ui_status: UiStatus::TitleMenu,
             ui_status_buffer: UiStatus::TitleMenu,
             text_set: Default::default(),
+            dice_result_shown_at: None,
+            transition_log: None,
+            practice_record: None,
         })
     }
     pub fn init(&mut self, preferences: &Preferences) -> Result<()> {
         self.text_set.set_guidance(preferences);
         self.text_set
-            .set_prompt_dice_roll(preferences, self.world.dice_max());
-        self.text_set.set_player_list(
-            preferences,
-            &self.current_player,
-            &self.player_order,
-            &self.player_status_table,
-        )?;
-        self.text_set.main_window = self.world.start_description(preferences);
+            .set_prompt_dice_roll(preferences, self.current_dice_max()?);
+        self.update_player_list(preferences)?;
+        self.text_set.main_window = self.current_area()?.preview_description(preferences);
+        Ok(())
+    }
+    /// 一人のプレイヤーがゴールするたびに最初からやり直す練習モードにする
+    pub fn enable_practice_mode(&mut self) -> Result<()> {
+        if self.player_order.len() != 1 {
+            return Err(GameSystemError::NotSinglePlayer(self.player_order.len()).into());
+        }
+        self.practice_record = Some(Default::default());
+        Ok(())
+    }
+    /// 現在のプレイヤーがいるマス
+    pub fn current_area(&self) -> Result<&Area> {
+        let current_player_position = self
+            .player_status_table
+            .get(&self.current_player)
+            .ok_or_else(|| GameSystemError::NotFoundPlayer(self.current_player.to_owned()))?
+            .position();
+        Ok(self
+            .world
+            .area_list()
+            .get(current_player_position)
+            .ok_or_else(|| {
+                GameSystemError::OutOfRangePosition(
+                    self.current_player.to_owned(),
+                    current_player_position,
+                )
+            })?)
+    }
+    /// 画面の遷移を`(遷移前, キー, 遷移後)`として記録するようにする（キー入力によらない遷移のキーは`None`）
+    pub fn enable_transition_log(&mut self) {
+        self.transition_log = Some(Vec::new());
+    }
+    pub fn write_transition_log(&self, file_path: &Path) -> Result<()> {
+        let mut buf_writer = BufWriter::new(
+            File::create(file_path)
+                .with_context(|| format!("failed to create {}", file_path.display()))?,
+        );
+        for (from, key, to) in self.transition_log.iter().flatten() {
+            match key {
+                Some(key) => writeln!(buf_writer, "{}\t{:?}\t{}", from, key, to)?,
+                None => writeln!(buf_writer, "{}\tTick\t{}", from, to)?,
+            }
+        }
         Ok(())
     }
     pub fn transition(&mut self, preferences: &Preferences, key: Key) -> Result<bool> {
+        self.logged_transition(preferences, key, Some(key))
+    }
+    /// `key`による遷移を行い、記録が有効な場合は`logged_key`を遷移のキーとして記録する
+    fn logged_transition(
+        &mut self,
+        preferences: &Preferences,
+        key: Key,
+        logged_key: Option<Key>,
+    ) -> Result<bool> {
+        let ui_status_before_transition =
+            self.transition_log.as_ref().map(|_| self.ui_status.clone());
+        let flag_loop_break = self.transition_without_log(preferences, key)?;
+        if let (Some(transition_log), Some(ui_status_before_transition)) =
+            (self.transition_log.as_mut(), ui_status_before_transition)
+        {
+            transition_log.push((
+                ui_status_before_transition,
+                logged_key,
+                self.ui_status.clone(),
+            ));
+        }
+        Ok(flag_loop_break)
+    }
+    fn transition_without_log(&mut self, preferences: &Preferences, key: Key) -> Result<bool> {
         let mut flag_loop_break = false;
         match &self.ui_status {
             UiStatus::TitleMenu => {
@@ -101,6 +199,36 @@ impl GameData {
         }
         Ok(flag_loop_break)
     }
+    /// キー入力がない間に呼ばれ、画面の再描画が必要な場合は`true`を返す
+    pub fn tick(&mut self, preferences: &Preferences, now: Instant) -> Result<bool> {
+        if self.should_auto_advance(preferences, now)? {
+            // 自動で進める場合はEnterキーと同じ遷移を、キー入力なしとして記録する
+            self.logged_transition(preferences, Key::Char('\n'), None)?;
+            Ok(true)
+        } else {
+            Ok(false)
+        }
+    }
+    /// サイコロの結果画面から自動で進めるかどうか
+    ///
+    /// 次のプレイヤーがお休みの場合は、確認のため自動では進めない。
+    pub fn should_auto_advance(&self, preferences: &Preferences, now: Instant) -> Result<bool> {
+        match (
+            preferences.auto_advance_delay(),
+            self.dice_result_shown_at,
+            &self.ui_status,
+        ) {
+            (Some(delay), Some(shown_at), UiStatus::DiceResult) => {
+                let num_skip_of_current_player = self
+                    .player_status_table
+                    .get(&self.current_player)
+                    .ok_or_else(|| GameSystemError::NotFoundPlayer(self.current_player.to_owned()))?
+                    .num_skip();
+                Ok(num_skip_of_current_player == 0 && now.duration_since(shown_at) >= delay)
+            }
+            _ => Ok(false),
+        }
+    }
 
     fn title_menu(&mut self, _preferences: &Preferences, key: Key) -> Result<()> {
         match key {
@@ -121,38 +249,48 @@ impl GameData {
         match key {
             Key::Char(c) => {
                 match c {
-                    '0' => {
-                        if !self.text_set.dice_string.is_empty() {
-                            self.text_set.dice_string.push(c);
-                            self.text_set
-                                .set_prompt_dice_roll(preferences, self.world.dice_max());
-                        }
+                    '0' if !self.text_set.dice_string.is_empty() => {
+                        self.text_set.dice_string.push(c);
+                        self.text_set
+                            .set_prompt_dice_roll(preferences, self.current_dice_max()?);
                     }
                     '1'..='9' => {
                         self.text_set.dice_string.push(c);
                         self.text_set
-                            .set_prompt_dice_roll(preferences, self.world.dice_max());
+                            .set_prompt_dice_roll(preferences, self.current_dice_max()?);
                     }
                     '\n' => {
                         if self.text_set.dice_string.is_empty() {
                             return Ok(());
                         }
                         self.text_set.set_prompt_enter(preferences);
+                        let dice = match self.text_set.dice_string.parse() {
+                            Ok(dice) => dice,
+                            // usizeに収まらないほど大きな値も範囲外として扱う
+                            Err(_) => {
+                                let dice_string = self.text_set.dice_string.clone();
+                                self.show_dice_out_of_range(preferences, &dice_string);
+                                return Ok(());
+                            }
+                        };
                         match self.world.dice_roll(
                             preferences,
-                            self.text_set.dice_string.parse()?,
+                            dice,
                             &self.current_player,
                             &self.player_order,
                             &mut self.player_status_table,
                         ) {
                             Ok(main_window_text) => {
                                 self.text_set.main_window = main_window_text;
+                                self.count_practice_turn();
                                 self.change_player()?;
+                                if let UiStatus::GameFinished = self.ui_status {
+                                    self.restart_practice(preferences);
+                                    self.update_player_list(preferences)?;
+                                }
                             }
                             Err(GameSystemError::OutOfRangeDice(dice)) => {
-                                self.ui_status = UiStatus::DiceResult;
-                                self.ui_status_buffer = UiStatus::DiceResult;
-                                self.text_set.set_dice_is_out_of_range(preferences, dice);
+                                self.show_dice_out_of_range(preferences, &dice.to_string());
                             }
                             Err(e) => return Err(e.into()),
                         }
@@ -163,11 +301,17 @@ impl GameData {
             Key::Backspace => {
                 self.text_set.dice_string.pop();
                 self.text_set
-                    .set_prompt_dice_roll(preferences, self.world.dice_max());
+                    .set_prompt_dice_roll(preferences, self.current_dice_max()?);
             }
             Key::Esc => {
-                self.ui_status_buffer = self.ui_status.clone();
-                self.ui_status = UiStatus::QuitMenu;
+                if self.text_set.dice_string.is_empty() {
+                    self.ui_status_buffer = self.ui_status.clone();
+                    self.ui_status = UiStatus::QuitMenu;
+                } else {
+                    self.text_set.dice_string.clear();
+                    self.text_set
+                        .set_prompt_dice_roll(preferences, self.current_dice_max()?);
+                }
             }
             Key::Ctrl('t') => {
                 self.ui_status_buffer = self.ui_status.clone();
@@ -186,6 +330,7 @@ impl GameData {
                     .get_mut(&self.current_player)
                     .ok_or_else(|| GameSystemError::NotFoundPlayer(self.current_player.to_owned()))?
                     .sub_num_skip(1);
+                self.count_practice_turn();
                 self.text_set.set_prompt_enter(preferences);
                 self.text_set.main_window.clear();
                 self.change_player()?;
@@ -218,7 +363,7 @@ impl GameData {
                     self.text_set.dice_string.clear();
                     self.text_set.main_window.clear();
                     self.text_set
-                        .set_prompt_dice_roll(preferences, self.world.dice_max());
+                        .set_prompt_dice_roll(preferences, self.current_dice_max()?);
                 } else {
                     self.ui_status = UiStatus::Skip;
                     self.ui_status_buffer = UiStatus::Skip;
@@ -226,12 +371,7 @@ impl GameData {
                     self.text_set
                         .set_skip_player(preferences, num_skip_of_current_player);
                 };
-                self.text_set.set_player_list(
-                    preferences,
-                    &self.current_player,
-                    &self.player_order,
-                    &self.player_status_table,
-                )?;
+                self.update_player_list(preferences)?;
             }
             Key::Esc => {
                 self.ui_status_buffer = self.ui_status.clone();
@@ -277,6 +417,60 @@ impl GameData {
         }
     }
 
+    fn update_player_list(&mut self, preferences: &Preferences) -> Result<()> {
+        self.text_set.set_player_list(
+            preferences,
+            &self.current_player,
+            &self.player_order,
+            &self.player_status_table,
+            self.world.has_point(),
+        )?;
+        if let Some(practice_record) = &self.practice_record {
+            self.text_set
+                .push_practice_record(preferences, practice_record);
+        }
+        Ok(())
+    }
+
+    fn count_practice_turn(&mut self) {
+        if let Some(practice_record) = self.practice_record.as_mut() {
+            practice_record.num_turn += 1;
+        }
+    }
+
+    /// 練習モードでゴールした場合に記録を更新し、スタートからやり直す
+    fn restart_practice(&mut self, preferences: &Preferences) {
+        if let Some(practice_record) = self.practice_record.as_mut() {
+            let num_turn = practice_record.num_turn;
+            practice_record.best_num_turn = Some(
+                practice_record
+                    .best_num_turn
+                    .map_or(num_turn, |best_num_turn| best_num_turn.min(num_turn)),
+            );
+            practice_record.num_turn = 0;
+            self.world.reset(&mut self.player_status_table);
+            self.ui_status = UiStatus::DiceResult;
+            self.ui_status_buffer = UiStatus::DiceResult;
+            self.dice_result_shown_at = Some(Instant::now());
+            self.text_set.push_practice_restart(preferences, num_turn);
+        }
+    }
+
+    fn current_dice_max(&self) -> Result<usize> {
+        Ok(self.world.player_dice_max(
+            self.player_status_table
+                .get(&self.current_player)
+                .ok_or_else(|| GameSystemError::NotFoundPlayer(self.current_player.to_owned()))?,
+        ))
+    }
+
+    fn show_dice_out_of_range(&mut self, preferences: &Preferences, dice: &str) {
+        self.ui_status = UiStatus::DiceResult;
+        self.ui_status_buffer = UiStatus::DiceResult;
+        self.dice_result_shown_at = Some(Instant::now());
+        self.text_set.set_dice_is_out_of_range(preferences, dice);
+    }
+
     fn change_player(&mut self) -> Result<()> {
         match self
             .player_order
@@ -285,6 +479,7 @@ impl GameData {
             Some(player) => {
                 self.ui_status = UiStatus::DiceResult;
                 self.ui_status_buffer = UiStatus::DiceResult;
+                self.dice_result_shown_at = Some(Instant::now());
                 self.current_player = player;
             }
             None => {
@@ -301,7 +496,8 @@ impl TextSet {
         self.guidance.clear();
         match preferences.language() {
             Language::Japanese => {
-                self.guidance.push_str("ESC: 終了\n");
+                self.guidance
+                    .push_str("ESC: 入力の取消（入力がない場合は終了）\n");
                 self.guidance.push_str("Ctrl-l: 再描画\n");
                 self.guidance.push_str("Ctrl-t: タイトル画面の表示");
             }
@@ -313,6 +509,7 @@ impl TextSet {
         current_player: &str,
         player_order: &[String],
         player_status_table: &HashMap<String, PlayerStatus>,
+        show_point: bool,
     ) -> Result<()> {
         const GOAL_MARK: &str = "🏁 ";
         const DICE_MARK: &str = "🎲 ";
@@ -325,11 +522,10 @@ impl TextSet {
         });
         self.player_list.push('\n');
         for player in player_order {
-            let order_of_arrival = player_status_table
+            let player_status = player_status_table
                 .get(player)
-                .ok_or_else(|| GameSystemError::NotFoundPlayer(player.to_owned()))?
-                .order_of_arrival();
-            match order_of_arrival {
+                .ok_or_else(|| GameSystemError::NotFoundPlayer(player.to_owned()))?;
+            match player_status.order_of_arrival() {
                 // Some(x) => self.player_list.push_str(&format!("{0:>2} ", x)),
                 // None => self.player_list.push_str(&format!("{0:>2} ", "")),
                 Some(x) => write!(self.player_list, "{0:>2} ", x).unwrap(),
@@ -341,10 +537,47 @@ impl TextSet {
                 self.player_list.push_str("   ");
             }
             self.player_list.push_str(player);
+            if player_status.has_veto() {
+                self.player_list.push_str(match preferences.language() {
+                    Language::Japanese => " [拒否権]",
+                });
+            }
+            if show_point {
+                write!(self.player_list, " ({}pt)", player_status.point()).unwrap();
+            }
             self.player_list.push('\n');
         }
         Ok(())
     }
+    fn push_practice_record(
+        &mut self,
+        preferences: &Preferences,
+        practice_record: &PracticeRecord,
+    ) {
+        match preferences.language() {
+            Language::Japanese => {
+                write!(self.player_list, "\n手数: {}\n", practice_record.num_turn).unwrap();
+                match practice_record.best_num_turn {
+                    Some(best_num_turn) => {
+                        writeln!(self.player_list, "最高記録: {}", best_num_turn).unwrap()
+                    }
+                    None => self.player_list.push_str("最高記録: -\n"),
+                }
+            }
+        }
+    }
+    fn push_practice_restart(&mut self, preferences: &Preferences, num_turn: u32) {
+        match preferences.language() {
+            Language::Japanese => {
+                write!(
+                    self.main_window,
+                    "\n{}手でゴールしました。スタートからやり直します。",
+                    num_turn
+                )
+                .unwrap();
+            }
+        }
+    }
     fn set_prompt_dice_roll(&mut self, preferences: &Preferences, dice_max: usize) {
         self.message.clear();
         match preferences.language() {
@@ -378,7 +611,7 @@ impl TextSet {
                 .push_str("全員ゴールしました。\nゲームを終了してください。"),
         }
     }
-    fn set_dice_is_out_of_range(&mut self, preferences: &Preferences, dice: usize) {
+    fn set_dice_is_out_of_range(&mut self, preferences: &Preferences, dice: &str) {
         match preferences.language() {
             Language::Japanese => {
                 self.main_window = format!("サイコロの値が範囲外です: {}", dice);
@@ -393,3 +626,248 @@ impl TextSet {
         }
     }
 }
+
+/// テスト用に、マスごとの効果の一覧とプレイヤーの位置の一覧から初期化済みのゲームの状態を作る
+#[cfg(test)]
+pub fn game_data_from_effect_list(
+    preferences: &Preferences,
+    effect_list: &[&[&str]],
+    position_list: &[(&str, usize)],
+) -> GameData {
+    let (player_order, player_status_table) =
+        crate::game_system::player_status::player_status_table_from_position_list(position_list);
+    let mut game_data = GameData::try_new(
+        crate::game_system::toml_interface::world_from_effect_list(3, effect_list),
+        player_order,
+        player_status_table,
+    )
+    .unwrap();
+    game_data.init(preferences).unwrap();
+    game_data
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game_system::player_status::player_status_table_from_position_list;
+    use crate::game_system::toml_interface::world_from_str;
+    use std::time::Duration;
+
+    #[test]
+    fn try_new_rejects_too_few_players() {
+        let world_toml = r#"
+            [general]
+            title = "テスト"
+            opening_msg = ""
+            start_description = "スタート"
+            goal_description = "ゴール"
+            dice_max = 3
+            min_players = 2
+
+            [[area]]
+            description = "1マス目"
+            "#;
+        let (player_order, player_status_table) =
+            player_status_table_from_position_list(&[("Alice", 0)]);
+        let error = GameData::try_new(
+            world_from_str(world_toml),
+            player_order,
+            player_status_table,
+        )
+        .unwrap_err();
+        assert!(matches!(
+            error.downcast_ref::<GameSystemError>(),
+            Some(GameSystemError::TooFewPlayers(2, 1))
+        ));
+        let (player_order, player_status_table) =
+            player_status_table_from_position_list(&[("Alice", 0), ("Bob", 0)]);
+        assert!(GameData::try_new(
+            world_from_str(world_toml),
+            player_order,
+            player_status_table
+        )
+        .is_ok());
+    }
+
+    #[test]
+    fn ui_status_display_names() {
+        let name_list: Vec<_> = [
+            UiStatus::QuitMenu,
+            UiStatus::TitleMenu,
+            UiStatus::DiceRoll,
+            UiStatus::Skip,
+            UiStatus::DiceResult,
+            UiStatus::GameFinished,
+        ]
+        .iter()
+        .map(|ui_status| ui_status.to_string())
+        .collect();
+        assert_eq!(
+            name_list,
+            vec![
+                "QuitMenu",
+                "TitleMenu",
+                "DiceRoll",
+                "Skip",
+                "DiceResult",
+                "GameFinished"
+            ]
+        );
+    }
+
+    #[test]
+    fn practice_restarts_and_keeps_best_turn_count() {
+        let preferences = Preferences::default();
+        let mut game_data = game_data_from_effect_list(&preferences, &[&[], &[]], &[("Alice", 0)]);
+        game_data.enable_practice_mode().unwrap();
+        game_data.transition(&preferences, Key::Char('\n')).unwrap();
+        let mut best_num_turn_list = Vec::new();
+        for dice_list in [&[1, 2][..], &[3], &[2, 1]] {
+            for dice in dice_list {
+                for key in [
+                    Key::Char(char::from_digit(*dice, 10).unwrap()),
+                    Key::Char('\n'),
+                    Key::Char('\n'),
+                ] {
+                    game_data.transition(&preferences, key).unwrap();
+                }
+            }
+            let practice_record = game_data.practice_record.unwrap();
+            assert_eq!(practice_record.num_turn, 0);
+            assert_eq!(game_data.player_status_table["Alice"].position(), 0);
+            best_num_turn_list.push(practice_record.best_num_turn);
+        }
+        assert_eq!(best_num_turn_list, vec![Some(2), Some(1), Some(1)]);
+    }
+
+    #[test]
+    fn practice_mode_needs_single_player() {
+        let preferences = Preferences::default();
+        let mut game_data =
+            game_data_from_effect_list(&preferences, &[&[], &[]], &[("Alice", 0), ("Bob", 0)]);
+        assert!(game_data.enable_practice_mode().is_err());
+    }
+
+    #[test]
+    fn current_area_follows_current_player_position() {
+        let preferences = Preferences::default();
+        let mut game_data =
+            game_data_from_effect_list(&preferences, &[&[], &[]], &[("Alice", 2), ("Bob", 0)]);
+        assert_eq!(game_data.current_area().unwrap().description(), "2マス目");
+        game_data
+            .player_status_table
+            .get_mut("Alice")
+            .unwrap()
+            .set_position(100);
+        assert!(matches!(
+            game_data
+                .current_area()
+                .unwrap_err()
+                .downcast_ref::<GameSystemError>(),
+            Some(GameSystemError::OutOfRangePosition(player, 100)) if player == "Alice"
+        ));
+    }
+
+    #[test]
+    fn auto_advance_waits_for_delay_and_skipped_player() {
+        let mut preferences = Preferences::default();
+        let mut game_data =
+            game_data_from_effect_list(&preferences, &[&[], &[]], &[("Alice", 0), ("Bob", 0)]);
+        for key in [Key::Char('\n'), Key::Char('1'), Key::Char('\n')] {
+            game_data.transition(&preferences, key).unwrap();
+        }
+        let shown_at = game_data.dice_result_shown_at.unwrap();
+        let delay = Duration::from_secs(2);
+        // 自動で進める設定がない場合は進めない
+        assert!(!game_data
+            .should_auto_advance(&preferences, shown_at + delay)
+            .unwrap());
+        preferences.set_auto_advance_delay(Some(delay));
+        assert!(!game_data
+            .should_auto_advance(&preferences, shown_at + Duration::from_secs(1))
+            .unwrap());
+        assert!(game_data
+            .should_auto_advance(&preferences, shown_at + delay)
+            .unwrap());
+        // 次のプレイヤーがお休みの場合は進めない
+        game_data
+            .player_status_table
+            .get_mut("Bob")
+            .unwrap()
+            .add_num_skip(1);
+        assert!(!game_data
+            .should_auto_advance(&preferences, shown_at + delay)
+            .unwrap());
+    }
+
+    #[test]
+    fn huge_dice_input_is_out_of_range() {
+        let preferences = Preferences::default();
+        let mut game_data =
+            game_data_from_effect_list(&preferences, &[&[], &[]], &[("Alice", 0), ("Bob", 0)]);
+        game_data.transition(&preferences, Key::Char('\n')).unwrap();
+        let dice_string = "1234567890".repeat(4);
+        for c in dice_string.chars().chain(['\n']) {
+            game_data.transition(&preferences, Key::Char(c)).unwrap();
+        }
+        assert!(matches!(game_data.ui_status, UiStatus::DiceResult));
+        assert_eq!(
+            game_data.text_set.main_window,
+            format!("サイコロの値が範囲外です: {}", dice_string)
+        );
+        assert_eq!(game_data.current_player, "Alice");
+        assert_eq!(game_data.player_status_table["Alice"].position(), 0);
+    }
+
+    #[test]
+    fn esc_clears_dice_input_before_opening_quit_menu() {
+        let preferences = Preferences::default();
+        let mut game_data =
+            game_data_from_effect_list(&preferences, &[&[], &[]], &[("Alice", 0), ("Bob", 0)]);
+        game_data.ui_status = UiStatus::DiceRoll;
+        game_data.transition(&preferences, Key::Char('2')).unwrap();
+        game_data.transition(&preferences, Key::Esc).unwrap();
+        assert!(game_data.text_set.dice_string.is_empty());
+        assert!(matches!(game_data.ui_status, UiStatus::DiceRoll));
+        game_data.transition(&preferences, Key::Esc).unwrap();
+        assert!(matches!(game_data.ui_status, UiStatus::QuitMenu));
+    }
+
+    #[test]
+    fn transition_log_records_keys_and_ticks() {
+        let mut preferences = Preferences::default();
+        preferences.set_auto_advance_delay(Some(Duration::from_secs(1)));
+        let mut game_data = game_data_from_effect_list(
+            &preferences,
+            &[&[], &[], &[], &[], &[]],
+            &[("Alice", 0), ("Bob", 0)],
+        );
+        game_data.enable_transition_log();
+        for key in [Key::Char('\n'), Key::Char('2'), Key::Char('\n')] {
+            game_data.transition(&preferences, key).unwrap();
+        }
+        let now = game_data.dice_result_shown_at.unwrap() + Duration::from_secs(1);
+        assert!(game_data.tick(&preferences, now).unwrap());
+        for key in [Key::Esc, Key::Char('n')] {
+            game_data.transition(&preferences, key).unwrap();
+        }
+        let transition_list: Vec<_> = game_data
+            .transition_log
+            .unwrap()
+            .into_iter()
+            .map(|(from, key, to)| (from.to_string(), key, to.to_string()))
+            .collect();
+        let expected_list: Vec<_> = [
+            ("TitleMenu", Some(Key::Char('\n')), "DiceRoll"),
+            ("DiceRoll", Some(Key::Char('2')), "DiceRoll"),
+            ("DiceRoll", Some(Key::Char('\n')), "DiceResult"),
+            ("DiceResult", None, "DiceRoll"),
+            ("DiceRoll", Some(Key::Esc), "QuitMenu"),
+            ("QuitMenu", Some(Key::Char('n')), "DiceRoll"),
+        ]
+        .into_iter()
+        .map(|(from, key, to)| (from.to_string(), key, to.to_string()))
+        .collect();
+        assert_eq!(transition_list, expected_list);
+    }
+}
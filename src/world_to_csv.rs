@@ -0,0 +1,169 @@
+// Copyright (c) 2023 Yuichi Ishida
+//
+// Released under the MIT license.
+// see https://opensource.org/licenses/mit-license.php
+
+use crate::game_system::toml_interface::read_world_from_file;
+use crate::game_system::world::World;
+use crate::preferences::Language;
+use anyhow::Result;
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::path::PathBuf;
+
+const FIELD_DELIMITER: char = ',';
+const EFFECT_DELIMITER: char = ';';
+
+pub fn run(world_file_path: PathBuf) -> Result<()> {
+    let world = read_world_from_file(&world_file_path)?;
+    let csv_file_name = world_file_path
+        .file_stem()
+        .unwrap()
+        .to_str()
+        .unwrap()
+        .to_owned()
+        + ".csv";
+    let csv_file_path = world_file_path.parent().unwrap().join(csv_file_name);
+    let mut buf_writer = BufWriter::new(File::create(csv_file_path)?);
+    write_csv(&mut buf_writer, &world)
+}
+
+/// マスごとに番号、説明、日本語の説明、ゴールの得点、効果の文字列を一行ずつ書き出す
+///
+/// 効果は一つずつ`;`を区切り文字として`escape_field`と同じ規則で囲んでから`;`でつなぐため、
+/// 引用された値に`;`や`"`が含まれていても元の一覧に戻せる。
+fn write_csv(buf_writer: &mut impl Write, world: &World) -> Result<()> {
+    write!(
+        buf_writer,
+        "index,description,description_ja,goal_bonus,effects\r\n"
+    )?;
+    for (i_area, area) in world.area_list().iter().enumerate() {
+        let effects = area
+            .effect_source_list()
+            .iter()
+            .map(|effect| escape_field(effect, EFFECT_DELIMITER))
+            .collect::<Vec<_>>()
+            .join(&EFFECT_DELIMITER.to_string());
+        write!(
+            buf_writer,
+            "{},{},{},{},{}\r\n",
+            i_area,
+            escape_field(area.description(), FIELD_DELIMITER),
+            escape_field(
+                area.localized_description(Language::Japanese)
+                    .unwrap_or_default(),
+                FIELD_DELIMITER
+            ),
+            area.goal_bonus()
+                .map(|goal_bonus| goal_bonus.to_string())
+                .unwrap_or_default(),
+            escape_field(&effects, FIELD_DELIMITER)
+        )?;
+    }
+    Ok(())
+}
+
+/// RFC 4180に従い、必要な場合はフィールドを二重引用符で囲む（区切り文字は`delimiter`）
+fn escape_field(field: &str, delimiter: char) -> String {
+    if field.contains([delimiter, '"', '\r', '\n']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game_system::toml_interface::world_from_str;
+
+    /// RFC 4180に従って一行を`delimiter`区切りのフィールドに分ける
+    fn split_record(record: &str, delimiter: char) -> Vec<String> {
+        let mut field_list = vec![String::new()];
+        let mut is_quoted = false;
+        let mut char_iter = record.chars().peekable();
+        while let Some(c) = char_iter.next() {
+            match c {
+                '"' if is_quoted && char_iter.peek() == Some(&'"') => {
+                    char_iter.next();
+                    field_list.last_mut().unwrap().push('"');
+                }
+                '"' => is_quoted = !is_quoted,
+                c if c == delimiter && !is_quoted => field_list.push(String::new()),
+                _ => field_list.last_mut().unwrap().push(c),
+            }
+        }
+        field_list
+    }
+
+    #[test]
+    fn escape_field_quotes_only_when_needed() {
+        assert_eq!(escape_field("abc", ','), "abc");
+        assert_eq!(escape_field("a,b", ','), "\"a,b\"");
+        assert_eq!(escape_field("a;b", ','), "a;b");
+        assert_eq!(escape_field("a;b", ';'), "\"a;b\"");
+        assert_eq!(escape_field("a\"b", ','), "\"a\"\"b\"");
+        assert_eq!(escape_field("a\nb", ','), "\"a\nb\"");
+    }
+
+    #[test]
+    fn csv_round_trips_every_column() {
+        let world = world_from_str(
+            r#"
+            [general]
+            title = "テスト"
+            opening_msg = ""
+            start_description = "スタート"
+            goal_description = "ゴール"
+            dice_max = 3
+
+            [[area]]
+            description = "雨, ときどき \"雪\""
+            description_ja = "あめ; ときどき ゆき"
+            goal_bonus = 5
+
+            [[area]]
+            description = "効果"
+            [[area.effect]]
+            element = "OnceOnly: PushSelf: num = 2"
+            [[area.effect]]
+            element = "SkipSelf: times = 1"
+            "#,
+        );
+        let mut output = Vec::new();
+        write_csv(&mut output, &world).unwrap();
+        let output = String::from_utf8(output).unwrap();
+        let record_list: Vec<_> = output.split_terminator("\r\n").collect();
+        assert_eq!(record_list.len(), 5);
+        assert_eq!(
+            record_list[0],
+            "index,description,description_ja,goal_bonus,effects"
+        );
+        assert_eq!(
+            split_record(record_list[2], FIELD_DELIMITER),
+            ["1", "雨, ときどき \"雪\"", "あめ; ときどき ゆき", "5", ""]
+        );
+        let field_list = split_record(record_list[3], FIELD_DELIMITER);
+        assert_eq!(field_list[..4], ["2", "効果", "", ""]);
+        assert_eq!(
+            split_record(&field_list[4], EFFECT_DELIMITER),
+            world.area_list()[2].effect_source_list()
+        );
+    }
+
+    #[test]
+    fn csv_keeps_effects_containing_delimiter_apart() {
+        let effect_source_list = ["A: text = \"x;y\"", "B: text = \"\"\"", "C"];
+        let effects = effect_source_list
+            .iter()
+            .map(|effect| escape_field(effect, EFFECT_DELIMITER))
+            .collect::<Vec<_>>()
+            .join(&EFFECT_DELIMITER.to_string());
+        let field = split_record(&escape_field(&effects, FIELD_DELIMITER), FIELD_DELIMITER);
+        assert_eq!(field.len(), 1);
+        assert_eq!(
+            split_record(&field[0], EFFECT_DELIMITER),
+            effect_source_list
+        );
+    }
+}
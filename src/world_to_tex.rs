@@ -22,29 +22,24 @@ pub fn run(world_file_path: PathBuf) -> Result<()> {
         + ".tex";
     let tex_file_path = world_file_path.parent().unwrap().join(tex_file_name);
     let mut buf_writer = BufWriter::new(File::create(tex_file_path)?);
+    writeln!(buf_writer, r#"\documentclass[11pt,dvipdfmx]{{jsarticle}}"#)?;
+    writeln!(buf_writer)?;
+    writeln!(buf_writer, r#"\usepackage{{tcolorbox}}"#)?;
     writeln!(
         buf_writer,
-        "{}",
-        r#"\documentclass[11pt,dvipdfmx]{jsarticle}"#
-    )?;
-    writeln!(buf_writer, "{}", r#""#)?;
-    writeln!(buf_writer, "{}", r#"\usepackage{tcolorbox}"#)?;
-    writeln!(
-        buf_writer,
-        "{}",
-        r#"\newtcolorbox{areabox}[2][]{colbacktitle=black,coltitle=white,title={#2}}"#
+        r#"\newtcolorbox{{areabox}}[2][]{{colbacktitle=black,coltitle=white,title={{#2}}}}"#
     )?;
-    writeln!(buf_writer, "{}", r#""#)?;
-    writeln!(buf_writer, "{}", r#"\begin{document}"#)?;
+    writeln!(buf_writer)?;
+    writeln!(buf_writer, r#"\begin{{document}}"#)?;
     writeln!(
         buf_writer,
         "{}",
         r#"\title{"#.to_owned() + world.title() + "}"
     )?;
-    writeln!(buf_writer, "{}", r#"\author{}"#)?;
-    writeln!(buf_writer, "{}", r#"\date{}"#)?;
-    writeln!(buf_writer, "{}", r#"\maketitle"#)?;
-    writeln!(buf_writer, "{}", r#""#)?;
+    writeln!(buf_writer, r#"\author{{}}"#)?;
+    writeln!(buf_writer, r#"\date{{}}"#)?;
+    writeln!(buf_writer, r#"\maketitle"#)?;
+    writeln!(buf_writer)?;
     for (i_area, area) in world.area_list().iter().enumerate() {
         writeln!(
             buf_writer,
@@ -54,9 +49,9 @@ pub fn run(world_file_path: PathBuf) -> Result<()> {
         for line in area.area_description(&preferences).lines() {
             writeln!(buf_writer, "{}\\\\", line)?;
         }
-        writeln!(buf_writer, "{}", r#"\end{areabox}"#)?;
-        writeln!(buf_writer, "{}", r#""#)?;
+        writeln!(buf_writer, r#"\end{{areabox}}"#)?;
+        writeln!(buf_writer)?;
     }
-    writeln!(buf_writer, "{}", r#"\end{document}"#)?;
+    writeln!(buf_writer, r#"\end{{document}}"#)?;
     Ok(())
 }